@@ -0,0 +1,54 @@
+//! Compares the const, byte-at-a-time validation in `AsciiStr::from_ascii_bytes()`
+//! with the vectorized validation used by `AsciiStr::from_ascii()`.
+//!
+//! Run with `cargo bench`.
+//! This uses a minimal timing loop instead of a benchmark framework so that it builds
+//! on the crate's minimum supported Rust version without extra dependencies.
+
+extern crate ascii;
+
+use std::time::{Duration, Instant};
+
+use ascii::{AsAsciiStrError, AsciiStr};
+
+/// Runs `f` on `input` until at least 200 ms have passed and returns the throughput in MB/s.
+fn throughput(input: &[u8], f: fn(&[u8]) -> Result<&AsciiStr, AsAsciiStrError>) -> f64 {
+    let mut iterations = 0u32;
+    let mut valid_up_to = 0;
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_millis(200) {
+        for _ in 0..16 {
+            // Use the result so the validation can't be optimized away.
+            valid_up_to += match f(input) {
+                Ok(s) => s.len(),
+                Err(e) => e.valid_up_to(),
+            };
+        }
+        iterations += 16;
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    assert!(valid_up_to > 0 || input.is_empty() || input[0] >= 0x80);
+    (input.len() as f64 * f64::from(iterations)) / elapsed / 1_000_000.0
+}
+
+fn main() {
+    let log_line = b"2023-05-04T12:34:56.789Z INFO request_id=8f14e45f method=GET path=/api/v1/items status=200 latency_ms=12\n";
+    let mut inputs: Vec<(String, Vec<u8>)> = Vec::new();
+    for &len in &[16, 64, 256, 4096, 1 << 20] {
+        let ascii: Vec<u8> = log_line.iter().copied().cycle().take(len).collect();
+        let mut late_error = ascii.clone();
+        late_error[len - 1] = 0xc3;
+        inputs.push((format!("ascii {} B", len), ascii));
+        inputs.push((format!("non-ascii at end {} B", len), late_error));
+    }
+
+    println!(
+        "{:<30} {:>16} {:>16}",
+        "input", "const (MB/s)", "fast (MB/s)"
+    );
+    for (name, input) in &inputs {
+        let slow = throughput(input, AsciiStr::from_ascii_bytes);
+        let fast = throughput(input, AsciiStr::from_ascii::<[u8]>);
+        println!("{:<30} {:>16.0} {:>16.0}", name, slow, fast);
+    }
+}
@@ -0,0 +1,543 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use ascii_char::AsciiChar;
+use ascii_str::AsciiStr;
+#[cfg(feature = "alloc")]
+use ascii_string::AsciiString;
+
+/// An ASCII string with a fixed capacity of `N` characters, stored inline.
+///
+/// This is an owned, growable ASCII string that doesn't need an allocator,
+/// so it's available without the `alloc` feature.
+/// It dereferences to [`AsciiStr`] and can be written to with `{}` formatting through
+/// [`fmt::Write`].
+///
+/// # Examples
+/// ```
+/// # use ascii::{AsciiArrayString, AsciiChar, AsciiStr};
+/// use std::fmt::Write;
+///
+/// let mut message = AsciiArrayString::<16>::new();
+/// write!(message, "T={}C", 21).unwrap();
+/// message.push(AsciiChar::LineFeed);
+/// assert_eq!(message, "T=21C\n");
+/// assert_eq!(message.remaining_capacity(), 10);
+///
+/// let overflow = AsciiStr::from_ascii("this won't fit in").unwrap();
+/// assert!(message.try_push_str(overflow).is_err());
+/// assert_eq!(message, "T=21C\n");
+/// ```
+#[derive(Clone, Copy)]
+pub struct AsciiArrayString<const N: usize> {
+    chars: [AsciiChar; N],
+    len: usize,
+}
+
+impl<const N: usize> AsciiArrayString<N> {
+    /// Creates a new, empty string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiArrayString;
+    /// const EMPTY: AsciiArrayString<8> = AsciiArrayString::new();
+    /// assert!(EMPTY.is_empty());
+    /// assert_eq!(EMPTY.capacity(), 8);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        AsciiArrayString {
+            chars: [AsciiChar::Null; N],
+            len: 0,
+        }
+    }
+
+    /// Creates a full string from an array of characters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiArrayString, AsciiChar};
+    /// const OK: AsciiArrayString<2> = AsciiArrayString::from_array([AsciiChar::O, AsciiChar::K]);
+    /// assert_eq!(OK, "OK");
+    /// assert!(OK.is_full());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn from_array(chars: [AsciiChar; N]) -> Self {
+        AsciiArrayString { chars, len: N }
+    }
+
+    /// Creates a string containing a copy of `string`.
+    ///
+    /// # Errors
+    /// Returns `Err` if `string` is longer than `N`.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[macro_use] extern crate ascii;
+    /// # use ascii::AsciiArrayString;
+    /// # fn main() {
+    /// const HELO: AsciiArrayString<32> = match AsciiArrayString::try_from_ascii_str(ascii!("HELO")) {
+    ///     Ok(string) => string,
+    ///     Err(_) => AsciiArrayString::new(),
+    /// };
+    /// assert_eq!(HELO, "HELO");
+    /// assert!(AsciiArrayString::<3>::try_from_ascii_str(ascii!("HELO")).is_err());
+    /// # }
+    /// ```
+    pub const fn try_from_ascii_str(string: &AsciiStr) -> Result<Self, CapacityError> {
+        #![allow(clippy::indexing_slicing)] // .get() is not const yet
+        let string = string.as_slice();
+        if string.len() > N {
+            return Err(CapacityError(()));
+        }
+        let mut chars = [AsciiChar::Null; N];
+        let mut i = 0;
+        while i < string.len() {
+            chars[i] = string[i];
+            i += 1;
+        }
+        Ok(AsciiArrayString {
+            chars,
+            len: string.len(),
+        })
+    }
+
+    /// Returns the number of characters the string can hold, which is `N`.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of characters in the string.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the string contains no characters.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if no more characters can be added to the string.
+    #[inline]
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns how many more characters can be added to the string.
+    #[inline]
+    #[must_use]
+    pub const fn remaining_capacity(&self) -> usize {
+        N - self.len
+    }
+
+    /// Returns the contents as an `AsciiStr`.
+    #[inline]
+    #[must_use]
+    pub fn as_ascii_str(&self) -> &AsciiStr {
+        // SAFETY: `len` is never larger than `N`.
+        unsafe { self.chars.get_unchecked(..self.len) }.into()
+    }
+
+    /// Returns the contents as a mutable `AsciiStr`.
+    #[inline]
+    #[must_use]
+    pub fn as_mut_ascii_str(&mut self) -> &mut AsciiStr {
+        // SAFETY: `len` is never larger than `N`.
+        unsafe { self.chars.get_unchecked_mut(..self.len) }.into()
+    }
+
+    /// Appends a character to the end of the string.
+    ///
+    /// # Errors
+    /// Returns `Err` if the string is full.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiArrayString, AsciiChar};
+    /// let mut s = AsciiArrayString::<1>::new();
+    /// assert!(s.try_push(AsciiChar::A).is_ok());
+    /// assert!(s.try_push(AsciiChar::B).is_err());
+    /// assert_eq!(s, "A");
+    /// ```
+    #[inline]
+    pub fn try_push(&mut self, ch: AsciiChar) -> Result<(), CapacityError> {
+        match self.chars.get_mut(self.len) {
+            Some(slot) => {
+                *slot = ch;
+                self.len += 1;
+                Ok(())
+            }
+            None => Err(CapacityError(())),
+        }
+    }
+
+    /// Appends a string to the end of this string.
+    ///
+    /// # Errors
+    /// Returns `Err` if `string` doesn't fit in the remaining capacity,
+    /// in which case nothing is appended.
+    pub fn try_push_str(&mut self, string: &AsciiStr) -> Result<(), CapacityError> {
+        let end = self.len + string.len();
+        match self.chars.get_mut(self.len..end) {
+            Some(slots) => {
+                slots.copy_from_slice(string.as_slice());
+                self.len = end;
+                Ok(())
+            }
+            None => Err(CapacityError(())),
+        }
+    }
+
+    /// Appends a character to the end of the string.
+    ///
+    /// # Panics
+    /// Panics if the string is full.
+    #[inline]
+    pub fn push(&mut self, ch: AsciiChar) {
+        self.try_push(ch).expect("AsciiArrayString is full");
+    }
+
+    /// Appends a string to the end of this string.
+    ///
+    /// # Panics
+    /// Panics if `string` doesn't fit in the remaining capacity.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiArrayString, AsciiStr};
+    /// let mut s = AsciiArrayString::<8>::new();
+    /// s.push_str(AsciiStr::from_ascii("ab").unwrap());
+    /// s.push_str(AsciiStr::from_ascii("cd").unwrap());
+    /// assert_eq!(s, "abcd");
+    /// ```
+    #[inline]
+    pub fn push_str(&mut self, string: &AsciiStr) {
+        self.try_push_str(string)
+            .expect("string doesn't fit in AsciiArrayString");
+    }
+
+    /// Removes the last character from the string and returns it,
+    /// or `None` if the string is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<AsciiChar> {
+        let ch = self.last()?;
+        self.len -= 1;
+        Some(ch)
+    }
+
+    /// Shortens the string to `new_len` characters.
+    ///
+    /// Does nothing if `new_len` is greater than the current length.
+    #[inline]
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len < self.len {
+            self.len = new_len;
+        }
+    }
+
+    /// Removes all characters from the string.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for AsciiArrayString<N> {
+    #[inline]
+    fn default() -> Self {
+        AsciiArrayString::new()
+    }
+}
+
+impl<const N: usize> Deref for AsciiArrayString<N> {
+    type Target = AsciiStr;
+
+    #[inline]
+    fn deref(&self) -> &AsciiStr {
+        self.as_ascii_str()
+    }
+}
+
+impl<const N: usize> DerefMut for AsciiArrayString<N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut AsciiStr {
+        self.as_mut_ascii_str()
+    }
+}
+
+impl<const N: usize> PartialEq for AsciiArrayString<N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ascii_str() == other.as_ascii_str()
+    }
+}
+
+impl<const N: usize> Eq for AsciiArrayString<N> {}
+
+impl<const N: usize> PartialOrd for AsciiArrayString<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for AsciiArrayString<N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_ascii_str().cmp(other.as_ascii_str())
+    }
+}
+
+impl<const N: usize> Hash for AsciiArrayString<N> {
+    /// Hashes the same way as `AsciiStr`, as required by `Borrow<AsciiStr>`.
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ascii_str().hash(state);
+    }
+}
+
+macro_rules! impl_partial_eq {
+    ($rhs:ty) => {
+        impl<const N: usize> PartialEq<$rhs> for AsciiArrayString<N> {
+            #[inline]
+            fn eq(&self, other: &$rhs) -> bool {
+                *self.as_ascii_str() == *other
+            }
+        }
+        impl<'a, const N: usize> PartialEq<&'a $rhs> for AsciiArrayString<N> {
+            #[inline]
+            fn eq(&self, other: &&'a $rhs) -> bool {
+                *self.as_ascii_str() == **other
+            }
+        }
+    };
+}
+
+impl_partial_eq! {AsciiStr}
+impl_partial_eq! {str}
+
+impl<const N: usize> fmt::Display for AsciiArrayString<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_ascii_str(), f)
+    }
+}
+
+impl<const N: usize> fmt::Debug for AsciiArrayString<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_ascii_str(), f)
+    }
+}
+
+/// Fails if the text isn't ASCII or doesn't fit, in which case nothing from that
+/// `write_str()` call is appended.
+impl<const N: usize> fmt::Write for AsciiArrayString<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let string = AsciiStr::from_ascii(s).map_err(|_| fmt::Error)?;
+        self.try_push_str(string).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const N: usize> AsRef<AsciiStr> for AsciiArrayString<N> {
+    #[inline]
+    fn as_ref(&self) -> &AsciiStr {
+        self
+    }
+}
+
+impl<const N: usize> AsMut<AsciiStr> for AsciiArrayString<N> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut AsciiStr {
+        self
+    }
+}
+
+impl<const N: usize> AsRef<[AsciiChar]> for AsciiArrayString<N> {
+    #[inline]
+    fn as_ref(&self) -> &[AsciiChar] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for AsciiArrayString<N> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const N: usize> AsRef<str> for AsciiArrayString<N> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> Borrow<AsciiStr> for AsciiArrayString<N> {
+    #[inline]
+    fn borrow(&self) -> &AsciiStr {
+        self
+    }
+}
+
+impl<const N: usize> BorrowMut<AsciiStr> for AsciiArrayString<N> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut AsciiStr {
+        self
+    }
+}
+
+impl<'a, const N: usize> TryFrom<&'a AsciiStr> for AsciiArrayString<N> {
+    type Error = CapacityError;
+
+    #[inline]
+    fn try_from(string: &'a AsciiStr) -> Result<Self, CapacityError> {
+        AsciiArrayString::try_from_ascii_str(string)
+    }
+}
+
+impl<const N: usize> From<[AsciiChar; N]> for AsciiArrayString<N> {
+    #[inline]
+    fn from(chars: [AsciiChar; N]) -> Self {
+        AsciiArrayString::from_array(chars)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> From<AsciiArrayString<N>> for AsciiString {
+    #[inline]
+    fn from(string: AsciiArrayString<N>) -> Self {
+        AsciiString::from(string.as_ascii_str())
+    }
+}
+
+impl<const N: usize> Extend<AsciiChar> for AsciiArrayString<N> {
+    /// # Panics
+    /// Panics if the characters don't fit in the remaining capacity.
+    fn extend<I: IntoIterator<Item = AsciiChar>>(&mut self, iterable: I) {
+        for ch in iterable {
+            self.push(ch);
+        }
+    }
+}
+
+/// Error returned when an [`AsciiArrayString`] doesn't have room for more characters.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CapacityError(());
+
+impl CapacityError {
+    /// Returns a description for this error, like `std::error::Error::description`.
+    #[cfg(not(feature = "std"))]
+    #[inline]
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub const fn description(&self) -> &'static str {
+        "insufficient capacity"
+    }
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        fmtr.write_str("insufficient capacity")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for CapacityError {
+    #[inline]
+    fn description(&self) -> &'static str {
+        "insufficient capacity"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AsciiArrayString;
+    use core::fmt::Write;
+    use {AsciiChar, AsciiStr};
+
+    #[test]
+    fn push_and_capacity() {
+        let mut s = AsciiArrayString::<4>::new();
+        assert_eq!((s.len(), s.capacity(), s.remaining_capacity()), (0, 4, 4));
+        s.push(AsciiChar::a);
+        s.push_str(AsciiStr::from_ascii("bc").unwrap());
+        assert!(s.try_push_str(AsciiStr::from_ascii("de").unwrap()).is_err());
+        assert_eq!(s, "abc");
+        s.push(AsciiChar::d);
+        assert!(s.is_full());
+        assert!(s.try_push(AsciiChar::e).is_err());
+        assert!(s.try_push_str(AsciiStr::from_ascii("").unwrap()).is_ok());
+        assert_eq!(s.pop(), Some(AsciiChar::d));
+        s.truncate(10);
+        s.truncate(1);
+        assert_eq!(s, "a");
+        s.clear();
+        assert_eq!(s.pop(), None);
+
+        let mut zero = AsciiArrayString::<0>::new();
+        assert!(zero.is_full());
+        assert!(zero.try_push(AsciiChar::a).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "AsciiArrayString is full")]
+    fn push_panics_when_full() {
+        let mut s = AsciiArrayString::<1>::from_array([AsciiChar::a]);
+        s.push(AsciiChar::b);
+    }
+
+    #[test]
+    fn write() {
+        let mut s = AsciiArrayString::<8>::new();
+        write!(s, "a-{:02}", 7).unwrap();
+        assert_eq!(s, "a-07");
+        assert!(s.write_str("é").is_err());
+        assert!(s.write_str("toolong").is_err());
+        assert_eq!(s, "a-07");
+    }
+
+    #[test]
+    fn comparisons() {
+        use core::cmp::Ordering;
+
+        let mut a = AsciiArrayString::from_array([AsciiChar::a, AsciiChar::a, AsciiChar::z]);
+        let b =
+            AsciiArrayString::<3>::try_from_ascii_str(AsciiStr::from_ascii("aa").unwrap()).unwrap();
+        // Characters beyond the length must not take part in comparisons.
+        a.truncate(2);
+        assert_eq!(a, b);
+        a.push(AsciiChar::b);
+        assert_eq!(a.cmp(&b), Ordering::Greater);
+        assert_eq!(&*b, AsciiStr::from_ascii("aa").unwrap());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_matches_ascii_str() {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        set.insert(AsciiArrayString::<8>::from_array([AsciiChar::X; 8]));
+        set.insert(AsciiArrayString::<8>::new());
+        assert!(set.contains(AsciiStr::from_ascii("XXXXXXXX").unwrap()));
+        assert!(set.contains(AsciiStr::from_ascii("").unwrap()));
+        assert!(!set.contains(AsciiStr::from_ascii("X").unwrap()));
+    }
+}
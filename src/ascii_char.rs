@@ -681,6 +681,53 @@ impl AsciiChar {
         self.is_ascii_digit() || ((*self as u8 | 0x20u8).wrapping_sub(b'a') < 6)
     }
 
+    /// Returns the value of this character as a hex digit,
+    /// or `None` if [`is_ascii_hexdigit()`](#method.is_ascii_hexdigit) returns false.
+    ///
+    /// Both uppercase and lowercase letters are accepted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiChar;
+    /// assert_eq!(AsciiChar::_7.to_hex_digit(), Some(7));
+    /// assert_eq!(AsciiChar::b.to_hex_digit(), Some(11));
+    /// assert_eq!(AsciiChar::F.to_hex_digit(), Some(15));
+    /// assert_eq!(AsciiChar::g.to_hex_digit(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn to_hex_digit(self) -> Option<u8> {
+        if !self.is_ascii_hexdigit() {
+            None
+        } else if self.is_ascii_digit() {
+            Some(self as u8 - b'0')
+        } else {
+            Some((self as u8 | 0x20) - b'a' + 10)
+        }
+    }
+
+    /// Returns the lowercase hex digit for `value`, or `None` if `value` is 16 or greater.
+    ///
+    /// Use [`to_ascii_uppercase()`](#method.to_ascii_uppercase) to get an uppercase digit.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiChar;
+    /// assert_eq!(AsciiChar::from_hex_digit(7), Some(AsciiChar::_7));
+    /// assert_eq!(AsciiChar::from_hex_digit(11), Some(AsciiChar::b));
+    /// assert_eq!(AsciiChar::from_hex_digit(16), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn from_hex_digit(value: u8) -> Option<AsciiChar> {
+        match value {
+            // SAFETY: The results are `0` to `9` and `a` to `f`.
+            0..=9 => Some(unsafe { AsciiChar::from_ascii_unchecked(b'0' + value) }),
+            10..=15 => Some(unsafe { AsciiChar::from_ascii_unchecked(b'a' + value - 10) }),
+            _ => None,
+        }
+    }
+
     /// Unicode has printable versions of the ASCII control codes, like '␛'.
     ///
     /// This function is identical with `.as_char()`
@@ -1022,6 +1069,7 @@ mod tests {
             assert_eq!(ascii.is_digit(36), ch.is_digit(36), "is_digit(36) {:?}", ch);
             assert_eq!(ascii.is_ascii_digit(), ch.is_ascii_digit());
             assert_eq!(ascii.is_ascii_hexdigit(), ch.is_ascii_hexdigit());
+            assert_eq!(ascii.to_hex_digit().map(u32::from), ch.to_digit(16));
             assert_eq!(ascii.is_ascii_control(), ch.is_ascii_control());
             assert_eq!(ascii.is_ascii_graphic(), ch.is_ascii_graphic());
             assert_eq!(ascii.is_ascii_punctuation(), ch.is_ascii_punctuation());
@@ -1048,6 +1096,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn from_hex_digit() {
+        for value in 0..16 {
+            let digit = AsciiChar::from_hex_digit(value).unwrap();
+            assert!(!digit.is_ascii_uppercase());
+            assert_eq!(digit.to_hex_digit(), Some(value));
+            assert_eq!(digit.to_ascii_uppercase().to_hex_digit(), Some(value));
+        }
+        assert_eq!(AsciiChar::from_hex_digit(16), None);
+        assert_eq!(AsciiChar::from_hex_digit(255), None);
+    }
+
     #[test]
     fn is_digit_strange_radixes() {
         assert_eq!(AsciiChar::_0.is_digit(0), '0'.is_digit(0));
@@ -0,0 +1,458 @@
+use alloc::borrow::{Borrow, ToOwned};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::ops::Deref;
+use core::{fmt, mem};
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+use ascii_char::AsciiChar;
+use ascii_str::{AsAsciiStr, AsAsciiStrError, AsciiStr};
+use ascii_string::{AsciiString, FromAsciiError, IntoAsciiString};
+
+/// A borrowed ASCII string that ends with exactly one NUL character, like a C string.
+///
+/// It combines the guarantees of [`AsciiStr`] and [`CStr`]: all characters are ASCII,
+/// the last one is [`AsciiChar::Null`] and none of the others are.
+/// It can therefore be passed to C functions through [`as_c_str()`](#method.as_c_str)
+/// or [`as_ptr()`](#method.as_ptr) without any checks or copying.
+///
+/// It dereferences to the `AsciiStr` without the terminating NUL.
+///
+/// This type is only available with the `std` feature.
+///
+/// # Examples
+/// ```
+/// # use ascii::{AsciiCStr, AsciiStr};
+/// use std::ffi::CStr;
+///
+/// let cstr = CStr::from_bytes_with_nul(b"hello\0").unwrap();
+/// let ascii = AsciiCStr::from_cstr(cstr).unwrap();
+/// assert_eq!(ascii.as_ascii_str(), "hello");
+/// assert_eq!(ascii.as_c_str(), cstr);
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct AsciiCStr {
+    /// Includes the terminating NUL.
+    slice: [AsciiChar],
+}
+
+impl AsciiCStr {
+    /// Converts a `CStr` into an `AsciiCStr` without copying it.
+    ///
+    /// # Errors
+    /// Returns `Err` if the string contains non-ASCII bytes.
+    pub fn from_cstr(cstr: &CStr) -> Result<&AsciiCStr, AsAsciiStrError> {
+        let with_nul = cstr.to_bytes_with_nul().as_ascii_str()?;
+        // SAFETY: `CStr` guarantees the only NUL is the last byte.
+        Ok(unsafe { AsciiCStr::from_ascii_str_with_nul_unchecked(with_nul) })
+    }
+
+    /// Returns the `AsciiCStr` that ends with the first NUL in `string`,
+    /// or `None` if `string` doesn't contain a NUL.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiCStr, AsciiStr};
+    /// let buffer = AsciiStr::from_ascii("name\0garbage\0").unwrap();
+    /// let name = AsciiCStr::from_ascii_str_until_nul(buffer).unwrap();
+    /// assert_eq!(name.as_ascii_str(), "name");
+    /// assert!(AsciiCStr::from_ascii_str_until_nul(name.as_ascii_str()).is_none());
+    /// ```
+    #[must_use]
+    pub fn from_ascii_str_until_nul(string: &AsciiStr) -> Option<&AsciiCStr> {
+        let nul = string.chars().position(|ch| ch == AsciiChar::Null)?;
+        let with_nul = string.as_slice().get(..=nul)?;
+        // SAFETY: `with_nul` ends with the first NUL in `string`.
+        Some(unsafe { AsciiCStr::from_ascii_str_with_nul_unchecked(with_nul.into()) })
+    }
+
+    /// Converts an `AsciiStr` that ends with a NUL into an `AsciiCStr` without checking it.
+    ///
+    /// # Safety
+    /// The last character of `string` must be the only `AsciiChar::Null` in it.
+    #[inline]
+    #[must_use]
+    pub const unsafe fn from_ascii_str_with_nul_unchecked(string: &AsciiStr) -> &AsciiCStr {
+        // SAFETY: `AsciiCStr` is a `repr(transparent)` wrapper around `[AsciiChar]`,
+        //         like `AsciiStr` is.
+        unsafe { mem::transmute(string) }
+    }
+
+    /// Returns the string without the terminating NUL.
+    #[inline]
+    #[must_use]
+    pub fn as_ascii_str(&self) -> &AsciiStr {
+        // There is always a NUL to split off.
+        let chars = self
+            .slice
+            .split_last()
+            .map_or(&[][..], |(_nul, chars)| chars);
+        chars.into()
+    }
+
+    /// Returns the string including the terminating NUL.
+    #[inline]
+    #[must_use]
+    pub fn as_ascii_str_with_nul(&self) -> &AsciiStr {
+        (&self.slice).into()
+    }
+
+    /// Converts the string into a `CStr` without copying or checking it.
+    #[inline]
+    #[must_use]
+    pub fn as_c_str(&self) -> &CStr {
+        // SAFETY: The string ends with its only NUL.
+        unsafe { CStr::from_bytes_with_nul_unchecked(self.as_ascii_str_with_nul().as_bytes()) }
+    }
+
+    /// Returns a pointer to the NUL-terminated string, which can be passed to C functions.
+    ///
+    /// The pointer is only valid as long as the `AsciiCStr` is.
+    #[inline]
+    #[must_use]
+    pub fn as_ptr(&self) -> *const c_char {
+        self.as_c_str().as_ptr()
+    }
+
+    /// Copies the string into an owned `AsciiCString`.
+    #[must_use]
+    pub fn to_ascii_cstring(&self) -> AsciiCString {
+        AsciiCString {
+            slice: Box::from(&self.slice),
+        }
+    }
+}
+
+impl Deref for AsciiCStr {
+    type Target = AsciiStr;
+
+    #[inline]
+    fn deref(&self) -> &AsciiStr {
+        self.as_ascii_str()
+    }
+}
+
+impl AsRef<AsciiStr> for AsciiCStr {
+    #[inline]
+    fn as_ref(&self) -> &AsciiStr {
+        self.as_ascii_str()
+    }
+}
+
+impl AsRef<CStr> for AsciiCStr {
+    #[inline]
+    fn as_ref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl<'a> From<&'a AsciiCStr> for &'a CStr {
+    #[inline]
+    fn from(cstr: &'a AsciiCStr) -> &'a CStr {
+        cstr.as_c_str()
+    }
+}
+
+impl<'a> TryFrom<&'a CStr> for &'a AsciiCStr {
+    type Error = AsAsciiStrError;
+
+    #[inline]
+    fn try_from(cstr: &'a CStr) -> Result<&'a AsciiCStr, AsAsciiStrError> {
+        AsciiCStr::from_cstr(cstr)
+    }
+}
+
+impl ToOwned for AsciiCStr {
+    type Owned = AsciiCString;
+
+    #[inline]
+    fn to_owned(&self) -> AsciiCString {
+        self.to_ascii_cstring()
+    }
+}
+
+impl fmt::Display for AsciiCStr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_ascii_str(), f)
+    }
+}
+
+impl fmt::Debug for AsciiCStr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_ascii_str(), f)
+    }
+}
+
+/// An owned ASCII string that ends with exactly one NUL character, like a C string.
+///
+/// This is the owned counterpart of [`AsciiCStr`], and can be converted into a [`CString`]
+/// and back without copying.
+///
+/// This type is only available with the `std` feature.
+///
+/// # Examples
+/// ```
+/// # use ascii::AsciiStr;
+/// use std::ffi::CString;
+///
+/// let path = AsciiStr::from_ascii("/dev/null").unwrap().to_ascii_cstring().unwrap();
+/// assert_eq!(path.as_c_str().to_bytes_with_nul(), b"/dev/null\0");
+/// let cstring = CString::from(path);
+/// assert_eq!(cstring.as_bytes(), b"/dev/null");
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AsciiCString {
+    /// Includes the terminating NUL.
+    slice: Box<[AsciiChar]>,
+}
+
+impl AsciiCString {
+    /// Converts a `CString` into an `AsciiCString` without copying it.
+    ///
+    /// # Errors
+    /// Returns the `CString` if it contains non-ASCII bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiCString;
+    /// use std::ffi::CString;
+    ///
+    /// let ascii = AsciiCString::from_cstring(CString::new("ok").unwrap()).unwrap();
+    /// assert_eq!(ascii.as_ascii_str(), "ok");
+    /// let error = AsciiCString::from_cstring(CString::new("n\u{e4}").unwrap()).unwrap_err();
+    /// assert_eq!(error.ascii_error().valid_up_to(), 1);
+    /// ```
+    pub fn from_cstring(cstring: CString) -> Result<AsciiCString, FromAsciiError<CString>> {
+        let mut string = cstring.into_ascii_string()?;
+        // The capacity still includes the NUL that was removed, so this doesn't reallocate,
+        // and neither does converting into a boxed slice.
+        string.push(AsciiChar::Null);
+        Ok(AsciiCString {
+            slice: Vec::from(string).into_boxed_slice(),
+        })
+    }
+
+    /// Returns the string as an `AsciiCStr`.
+    #[inline]
+    #[must_use]
+    pub fn as_ascii_cstr(&self) -> &AsciiCStr {
+        // SAFETY: The string ends with its only NUL.
+        unsafe { AsciiCStr::from_ascii_str_with_nul_unchecked((&*self.slice).into()) }
+    }
+
+    /// Converts the string into a `CString` without copying it.
+    #[must_use]
+    pub fn into_cstring(self) -> CString {
+        let mut string = AsciiString::from(self.slice.into_vec());
+        let _nul = string.pop();
+        // SAFETY: The only NUL was the last character, which was just removed.
+        //         The capacity still has room for it, so adding it back doesn't reallocate.
+        unsafe { CString::from_vec_unchecked(string.into()) }
+    }
+
+    /// Converts the string into an `AsciiString` without the terminating NUL.
+    #[must_use]
+    pub fn into_ascii_string(self) -> AsciiString {
+        let mut string = AsciiString::from(self.slice.into_vec());
+        let _nul = string.pop();
+        string
+    }
+}
+
+impl Deref for AsciiCString {
+    type Target = AsciiCStr;
+
+    #[inline]
+    fn deref(&self) -> &AsciiCStr {
+        self.as_ascii_cstr()
+    }
+}
+
+impl Borrow<AsciiCStr> for AsciiCString {
+    #[inline]
+    fn borrow(&self) -> &AsciiCStr {
+        self.as_ascii_cstr()
+    }
+}
+
+impl AsRef<AsciiCStr> for AsciiCString {
+    #[inline]
+    fn as_ref(&self) -> &AsciiCStr {
+        self.as_ascii_cstr()
+    }
+}
+
+impl AsRef<AsciiStr> for AsciiCString {
+    #[inline]
+    fn as_ref(&self) -> &AsciiStr {
+        self.as_ascii_str()
+    }
+}
+
+impl AsRef<CStr> for AsciiCString {
+    #[inline]
+    fn as_ref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl From<AsciiCString> for CString {
+    #[inline]
+    fn from(string: AsciiCString) -> CString {
+        string.into_cstring()
+    }
+}
+
+impl From<AsciiCString> for AsciiString {
+    #[inline]
+    fn from(string: AsciiCString) -> AsciiString {
+        string.into_ascii_string()
+    }
+}
+
+impl<'a> From<&'a AsciiCStr> for AsciiCString {
+    #[inline]
+    fn from(cstr: &'a AsciiCStr) -> AsciiCString {
+        cstr.to_ascii_cstring()
+    }
+}
+
+impl TryFrom<CString> for AsciiCString {
+    type Error = FromAsciiError<CString>;
+
+    #[inline]
+    fn try_from(cstring: CString) -> Result<AsciiCString, FromAsciiError<CString>> {
+        AsciiCString::from_cstring(cstring)
+    }
+}
+
+impl fmt::Display for AsciiCString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_ascii_str(), f)
+    }
+}
+
+impl fmt::Debug for AsciiCString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_ascii_str(), f)
+    }
+}
+
+/// Copies `string` and appends a NUL, if it doesn't already contain one.
+pub(crate) fn to_ascii_cstring(string: &AsciiStr) -> Result<AsciiCString, InteriorNulError> {
+    if let Some(position) = string.chars().position(|ch| ch == AsciiChar::Null) {
+        return Err(InteriorNulError { position });
+    }
+    let mut chars = Vec::with_capacity(string.len() + 1);
+    chars.extend_from_slice(string.as_slice());
+    chars.push(AsciiChar::Null);
+    Ok(AsciiCString {
+        slice: chars.into_boxed_slice(),
+    })
+}
+
+/// Error returned by [`AsciiStr::to_ascii_cstring()`] when the string contains a NUL.
+///
+/// # Examples
+/// ```
+/// # use ascii::AsciiStr;
+/// let error = AsciiStr::from_ascii("a\0b").unwrap().to_ascii_cstring().unwrap_err();
+/// assert_eq!(error.nul_position(), 1);
+/// assert_eq!(error.to_string(), "interior NUL at index 1");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InteriorNulError {
+    position: usize,
+}
+
+impl InteriorNulError {
+    /// Returns the index of the first NUL in the string.
+    #[inline]
+    #[must_use]
+    pub const fn nul_position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for InteriorNulError {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmtr, "interior NUL at index {}", self.position)
+    }
+}
+
+impl Error for InteriorNulError {
+    #[inline]
+    fn description(&self) -> &'static str {
+        "string contains an interior NUL"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsciiCStr, AsciiCString};
+    use std::ffi::{CStr, CString};
+    use {AsciiChar, AsciiStr, AsciiString};
+
+    #[test]
+    fn to_ascii_cstring() {
+        let string = AsciiStr::from_ascii("abc").unwrap();
+        let cstring = string.to_ascii_cstring().unwrap();
+        assert_eq!(cstring.as_ascii_str(), string);
+        assert_eq!(cstring.as_ascii_str_with_nul(), "abc\0");
+        assert_eq!(cstring.len(), 3);
+        let empty = AsciiStr::from_ascii("")
+            .unwrap()
+            .to_ascii_cstring()
+            .unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.as_c_str().to_bytes_with_nul(), b"\0");
+
+        for (s, position) in &[("\0", 0), ("ab\0", 2), ("a\0b\0", 1)] {
+            let error = AsciiStr::from_ascii(s).unwrap().to_ascii_cstring();
+            assert_eq!(error.unwrap_err().nul_position(), *position);
+        }
+    }
+
+    #[test]
+    fn borrowed() {
+        let cstr = CStr::from_bytes_with_nul(b"ok\0").unwrap();
+        let ascii = AsciiCStr::from_cstr(cstr).unwrap();
+        assert_eq!(ascii.as_ptr(), cstr.as_ptr());
+        assert_eq!(ascii.to_string(), "ok");
+        let cstr = CStr::from_bytes_with_nul(b"\xc3\xa9\0").unwrap();
+        assert_eq!(AsciiCStr::from_cstr(cstr).unwrap_err().valid_up_to(), 0);
+
+        let until_nul = AsciiCStr::from_ascii_str_until_nul(AsciiStr::from_ascii("\0").unwrap());
+        assert!(until_nul.unwrap().is_empty());
+    }
+
+    #[test]
+    fn owned_conversions_reuse_the_allocation() {
+        let cstring = CString::new("some bytes").unwrap();
+        let ptr = cstring.as_ptr();
+        let ascii = AsciiCString::from_cstring(cstring).unwrap();
+        assert_eq!(ascii.as_ptr(), ptr);
+        let cstring = ascii.into_cstring();
+        assert_eq!(cstring.as_ptr(), ptr);
+        assert_eq!(cstring.as_bytes(), b"some bytes");
+
+        let error = AsciiCString::from_cstring(CString::new(&b"a\xff"[..]).unwrap()).unwrap_err();
+        assert_eq!(error.into_source().as_bytes(), b"a\xff");
+
+        let ascii = AsciiStr::from_ascii("x")
+            .unwrap()
+            .to_ascii_cstring()
+            .unwrap();
+        let copy = ascii.as_ascii_cstr().to_owned();
+        assert_eq!(copy, ascii);
+        assert_eq!(AsciiString::from(copy).as_slice(), &[AsciiChar::x][..]);
+    }
+}
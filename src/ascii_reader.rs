@@ -0,0 +1,381 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read};
+
+use ascii_char::AsciiChar;
+use ascii_str::{AsAsciiStr, AsAsciiStrError};
+use ascii_string::AsciiString;
+
+/// A reader adapter that validates the bytes it reads as ASCII.
+///
+/// Input is validated one buffer at a time as it is read, so no unvalidated copy of it is kept
+/// around.
+/// When a non-ASCII byte is found, the methods return an [`io::Error`] of kind
+/// [`InvalidData`](io::ErrorKind::InvalidData) that wraps an
+/// [`AsAsciiStrError`](crate::AsAsciiStrError) whose
+/// [`valid_up_to()`](crate::AsAsciiStrError::valid_up_to) is the offset of that byte from the
+/// start of the stream.
+///
+/// # Examples
+/// ```
+/// # use ascii::{AsAsciiStrError, AsciiReader, AsciiString};
+/// let mut reader = AsciiReader::new(&b"HELO example.com\r\nMAIL FROM:<jos\xe9@example.com>\r\n"[..]);
+/// let mut line = AsciiString::new();
+/// assert_eq!(reader.read_ascii_line(&mut line).unwrap(), 18);
+/// assert_eq!(line, "HELO example.com\r\n");
+///
+/// let error = reader.read_ascii_line(&mut line).unwrap_err();
+/// let error = error.get_ref().unwrap().downcast_ref::<AsAsciiStrError>().unwrap();
+/// assert_eq!(error.valid_up_to(), 32);
+/// assert_eq!(error.non_ascii_byte(), Some(0xe9));
+/// assert_eq!(line, "HELO example.com\r\n");
+/// ```
+pub struct AsciiReader<R> {
+    inner: BufReader<R>,
+    /// The number of bytes consumed from `inner`.
+    position: u64,
+}
+
+impl<R: Read> AsciiReader<R> {
+    /// Creates a new `AsciiReader` with a default buffer capacity.
+    #[inline]
+    #[must_use]
+    pub fn new(inner: R) -> Self {
+        AsciiReader {
+            inner: BufReader::new(inner),
+            position: 0,
+        }
+    }
+
+    /// Creates a new `AsciiReader` that reads `capacity` bytes at a time.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize, inner: R) -> Self {
+        AsciiReader {
+            inner: BufReader::with_capacity(capacity, inner),
+            position: 0,
+        }
+    }
+
+    /// Reads bytes until a newline (the `0x0A` byte) or EOF is reached,
+    /// and appends them to `buf`.
+    ///
+    /// The newline is included in `buf`, and the number of bytes read is returned.
+    /// `Ok(0)` means the end of the stream was reached.
+    ///
+    /// # Errors
+    /// If the line contains a non-ASCII byte, the whole line is still consumed,
+    /// `buf` is left unchanged and an [`InvalidData`](io::ErrorKind::InvalidData) error
+    /// wrapping an [`AsAsciiStrError`](crate::AsAsciiStrError) is returned.
+    ///
+    /// Errors from the underlying reader are returned as-is, except for
+    /// [`Interrupted`](io::ErrorKind::Interrupted) which is retried.
+    /// The ASCII data read before such an error has been appended to `buf`.
+    pub fn read_ascii_line(&mut self, buf: &mut AsciiString) -> io::Result<usize> {
+        self.read_ascii_until(buf, true)
+    }
+
+    /// Reads all bytes until EOF and appends them to `buf`.
+    ///
+    /// Returns the number of bytes read.
+    ///
+    /// # Errors
+    /// If the stream contains a non-ASCII byte, the rest of the stream is still consumed,
+    /// `buf` is left unchanged and an [`InvalidData`](io::ErrorKind::InvalidData) error
+    /// wrapping an [`AsAsciiStrError`](crate::AsAsciiStrError) for the first such byte is returned.
+    ///
+    /// Errors from the underlying reader are returned as-is, except for
+    /// [`Interrupted`](io::ErrorKind::Interrupted) which is retried.
+    /// The ASCII data read before such an error has been appended to `buf`.
+    pub fn read_to_ascii_string(&mut self, buf: &mut AsciiString) -> io::Result<usize> {
+        self.read_ascii_until(buf, false)
+    }
+
+    /// Returns an iterator over the lines of the stream.
+    ///
+    /// The lines don't include the trailing `\n` or `\r\n`.
+    /// A line that contains a non-ASCII byte is yielded as an error and skipped,
+    /// so iteration can continue with the next line.
+    #[inline]
+    #[must_use]
+    pub fn ascii_lines(self) -> AsciiLines<R> {
+        AsciiLines { reader: self }
+    }
+
+    fn read_ascii_until(
+        &mut self,
+        buf: &mut AsciiString,
+        stop_at_newline: bool,
+    ) -> io::Result<usize> {
+        let start_len = buf.len();
+        let mut read = 0;
+        let mut error: Option<NonAsciiBytes> = None;
+        loop {
+            let (done, used) = {
+                let available = match self.inner.fill_buf() {
+                    Ok(available) => available,
+                    Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(e),
+                };
+                let newline = if stop_at_newline {
+                    available.iter().position(|&b| b == b'\n')
+                } else {
+                    None
+                };
+                let (done, chunk) = match newline {
+                    Some(newline) => (true, available.split_at(newline + 1).0),
+                    None => (available.is_empty(), available),
+                };
+                match error {
+                    None => match chunk.as_ascii_str() {
+                        Ok(ascii) => buf.push_str(ascii),
+                        Err(e) => error = Some(NonAsciiBytes::new(self.position, chunk, e)),
+                    },
+                    Some(ref mut error) => error.append(chunk),
+                }
+                (done, chunk.len())
+            };
+            self.inner.consume(used);
+            self.position += used as u64;
+            read += used;
+            if done {
+                break;
+            }
+        }
+        match error {
+            Some(error) => {
+                buf.truncate(start_len);
+                Err(error.into_error())
+            }
+            None => Ok(read),
+        }
+    }
+}
+
+impl<R> AsciiReader<R> {
+    /// Returns the number of bytes consumed from the underlying reader so far.
+    ///
+    /// This is the offset that errors are relative to.
+    #[inline]
+    #[must_use]
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Gets a reference to the underlying reader.
+    #[inline]
+    #[must_use]
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from it will make [`position()`](#method.position) and the offsets
+    /// of later errors no longer match the stream.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner.get_mut()
+    }
+
+    /// Unwraps this `AsciiReader`, returning the underlying reader.
+    ///
+    /// Any data that was buffered but not yet read is lost.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for AsciiReader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AsciiReader")
+            .field("inner", &self.inner)
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+/// The first non-ASCII byte of a stream and up to three bytes after it,
+/// so that a character split across two buffers is still reported whole.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct NonAsciiBytes {
+    /// The stream offset of the first byte.
+    offset: u64,
+    bytes: [u8; 4],
+    len: usize,
+}
+
+impl NonAsciiBytes {
+    /// Captures the non-ASCII byte that `error` found in `chunk`,
+    /// where `position` is the stream offset of `chunk`.
+    pub(crate) fn new(position: u64, chunk: &[u8], error: AsAsciiStrError) -> Self {
+        let mut captured = NonAsciiBytes {
+            offset: position + error.valid_up_to() as u64,
+            bytes: [0; 4],
+            len: 0,
+        };
+        captured.append(chunk.split_at(error.valid_up_to()).1);
+        captured
+    }
+
+    /// Appends as many bytes from the start of `chunk` as there is room for.
+    pub(crate) fn append(&mut self, chunk: &[u8]) {
+        for (dst, &src) in self.bytes.iter_mut().skip(self.len).zip(chunk) {
+            *dst = src;
+            self.len += 1;
+        }
+    }
+
+    /// Returns whether enough bytes have been captured for the character they start.
+    #[cfg(any(feature = "futures-io", feature = "tokio"))]
+    pub(crate) fn is_complete(&self) -> bool {
+        let needed = match self.bytes[0] {
+            0xc2..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf4 => 4,
+            _ => 1,
+        };
+        self.len >= needed
+    }
+
+    /// Creates the [`InvalidData`](io::ErrorKind::InvalidData) error for these bytes.
+    pub(crate) fn into_error(self) -> io::Error {
+        // Offsets that don't fit in an `AsAsciiStrError` are reported as `usize::MAX`.
+        let offset = usize::try_from(self.offset).unwrap_or(usize::MAX);
+        let error = AsAsciiStrError::new(self.bytes.split_at(self.len).0, 0).with_offset(offset);
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+/// An iterator over the lines of an [`AsciiReader`].
+///
+/// This struct is created by [`AsciiReader::ascii_lines()`].
+#[derive(Debug)]
+pub struct AsciiLines<R> {
+    reader: AsciiReader<R>,
+}
+
+impl<R: Read> Iterator for AsciiLines<R> {
+    type Item = io::Result<AsciiString>;
+
+    fn next(&mut self) -> Option<io::Result<AsciiString>> {
+        let mut line = AsciiString::new();
+        match self.reader.read_ascii_line(&mut line) {
+            Ok(0) => None,
+            Ok(_) => {
+                if line.last() == Some(AsciiChar::LineFeed) {
+                    let _lf = line.pop();
+                    if line.last() == Some(AsciiChar::CarriageReturn) {
+                        let _cr = line.pop();
+                    }
+                }
+                Some(Ok(line))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AsciiReader;
+    use std::io::{self, Read};
+    use {AsAsciiStrError, AsciiString};
+
+    fn ascii_error(error: &io::Error) -> AsAsciiStrError {
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        *error.get_ref().unwrap().downcast_ref().unwrap()
+    }
+
+    /// Returns at most two bytes per read, and fails every other read with `Interrupted`.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl<'a> Read for Trickle<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "again"));
+            }
+            let n = self.data.len().min(buf.len()).min(2);
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn read_ascii_line() {
+        let mut reader = AsciiReader::new(&b"one\ntwo\r\nthree"[..]);
+        let mut line = AsciiString::new();
+        assert_eq!(reader.read_ascii_line(&mut line).unwrap(), 4);
+        assert_eq!(reader.read_ascii_line(&mut line).unwrap(), 5);
+        assert_eq!(line, "one\ntwo\r\n");
+        line.clear();
+        assert_eq!(reader.read_ascii_line(&mut line).unwrap(), 5);
+        assert_eq!(line, "three");
+        assert_eq!(reader.read_ascii_line(&mut line).unwrap(), 0);
+        assert_eq!(reader.position(), 14);
+    }
+
+    #[test]
+    fn error_offsets_span_buffers() {
+        let data = b"abc\ndef\nghi\xffjk\nlmn\n";
+        let reader = Trickle {
+            data: &data[..],
+            interrupt: false,
+        };
+        let mut reader = AsciiReader::with_capacity(3, reader);
+        let mut line = AsciiString::new();
+        assert_eq!(reader.read_ascii_line(&mut line).unwrap(), 4);
+        assert_eq!(reader.read_ascii_line(&mut line).unwrap(), 4);
+        let error = reader.read_ascii_line(&mut line).unwrap_err();
+        assert_eq!(ascii_error(&error).valid_up_to(), 11);
+        assert_eq!(ascii_error(&error).non_ascii_byte(), Some(0xff));
+        assert_eq!(line, "abc\ndef\n");
+        assert_eq!(reader.position(), 15);
+        assert_eq!(reader.read_ascii_line(&mut line).unwrap(), 4);
+        assert_eq!(line, "abc\ndef\nlmn\n");
+    }
+
+    #[test]
+    fn ascii_lines() {
+        let reader = AsciiReader::with_capacity(4, &b"first\r\n\nbad\x80\r\nlast"[..]);
+        let mut lines = reader.ascii_lines();
+        assert_eq!(lines.next().unwrap().unwrap(), "first");
+        assert_eq!(lines.next().unwrap().unwrap(), "");
+        let error = lines.next().unwrap().unwrap_err();
+        assert_eq!(ascii_error(&error).valid_up_to(), 11);
+        assert_eq!(lines.next().unwrap().unwrap(), "last");
+        assert!(lines.next().is_none());
+    }
+
+    #[test]
+    fn read_to_ascii_string() {
+        let mut reader = AsciiReader::with_capacity(2, &b"some\ntext"[..]);
+        let mut text = AsciiString::from_ascii("> ").unwrap();
+        assert_eq!(reader.read_to_ascii_string(&mut text).unwrap(), 9);
+        assert_eq!(text, "> some\ntext");
+
+        let mut reader = AsciiReader::with_capacity(2, &b"some\nt\xc3\xa9xt\xff"[..]);
+        let mut text = AsciiString::new();
+        let error = reader.read_to_ascii_string(&mut text).unwrap_err();
+        assert_eq!(ascii_error(&error).valid_up_to(), 6);
+        assert_eq!(ascii_error(&error).non_ascii_char(), Some('é'));
+        assert!(text.is_empty());
+        assert_eq!(reader.position(), 11);
+        assert_eq!(reader.into_inner(), b"");
+
+        // The 'é' is split across two buffers.
+        let mut reader = AsciiReader::with_capacity(7, &b"some\nt\xc3\xa9xt\xff"[..]);
+        let error = reader.read_to_ascii_string(&mut text).unwrap_err();
+        assert_eq!(ascii_error(&error).valid_up_to(), 6);
+        assert_eq!(ascii_error(&error).non_ascii_char(), Some('é'));
+        assert_eq!(ascii_error(&error).error_len(), Some(2));
+    }
+}
@@ -0,0 +1,439 @@
+use core::fmt;
+use core::iter::FromIterator;
+use core::ops::{BitAnd, BitOr, Not, Sub};
+
+use ascii_char::AsciiChar;
+
+/// A set of [`AsciiChar`]s, stored as a 128-bit mask.
+///
+/// Sets can be built in `const` context, and combined with the set algebra methods
+/// or the `|`, `&`, `-` and `!` operators.
+/// They can be used as a pattern in the search, split and trim methods of
+/// [`AsciiStr`](crate::AsciiStr), where they match any character in the set.
+///
+/// # Examples
+/// ```
+/// # use ascii::{AsciiChar, AsciiSet, AsciiStr};
+/// const IDENT_START: AsciiSet = AsciiSet::ALPHA.with(AsciiChar::UnderScore);
+/// const IDENT: AsciiSet = IDENT_START.union(AsciiSet::DIGIT);
+///
+/// let s = AsciiStr::from_ascii("  _tmp1 = 2").unwrap();
+/// assert_eq!(s.find(IDENT_START), Some(2));
+/// assert_eq!(s.trim_matches(AsciiSet::WHITESPACE), "_tmp1 = 2");
+/// assert!(IDENT.contains(AsciiChar::_1));
+/// assert!(!IDENT.contains(AsciiChar::Equal));
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AsciiSet {
+    bits: u128,
+}
+
+impl AsciiSet {
+    /// The set that contains no characters.
+    pub const EMPTY: AsciiSet = AsciiSet { bits: 0 };
+    /// The set that contains all 128 ASCII characters.
+    pub const ALL: AsciiSet = AsciiSet { bits: u128::MAX };
+    /// The uppercase letters `A-Z`.
+    pub const UPPERCASE: AsciiSet = AsciiSet::from_range(AsciiChar::A, AsciiChar::Z);
+    /// The lowercase letters `a-z`.
+    pub const LOWERCASE: AsciiSet = AsciiSet::from_range(AsciiChar::a, AsciiChar::z);
+    /// The letters `A-Z` and `a-z`.
+    pub const ALPHA: AsciiSet = AsciiSet::UPPERCASE.union(AsciiSet::LOWERCASE);
+    /// The decimal digits `0-9`.
+    pub const DIGIT: AsciiSet = AsciiSet::from_range(AsciiChar::_0, AsciiChar::_9);
+    /// The hexadecimal digits `0-9`, `A-F` and `a-f`.
+    pub const HEXDIG: AsciiSet = AsciiSet::DIGIT
+        .union(AsciiSet::from_range(AsciiChar::A, AsciiChar::F))
+        .union(AsciiSet::from_range(AsciiChar::a, AsciiChar::f));
+    /// The letters and digits.
+    pub const ALPHANUMERIC: AsciiSet = AsciiSet::ALPHA.union(AsciiSet::DIGIT);
+    /// The printable characters except space, `!` to `~`.
+    pub const GRAPHIC: AsciiSet = AsciiSet::from_range(AsciiChar::Exclamation, AsciiChar::Tilde);
+    /// The printable characters including space.
+    pub const PRINTABLE: AsciiSet = AsciiSet::GRAPHIC.with(AsciiChar::Space);
+    /// The graphic characters that are not alphanumeric.
+    pub const PUNCT: AsciiSet = AsciiSet::GRAPHIC.difference(AsciiSet::ALPHANUMERIC);
+    /// The control characters `0x00-0x1f` and `DEL`.
+    pub const CONTROL: AsciiSet = AsciiSet::PRINTABLE.complement();
+    /// Space and tab.
+    pub const BLANK: AsciiSet = AsciiSet::from_chars(&[AsciiChar::Space, AsciiChar::Tab]);
+    /// The characters [`AsciiChar::is_ascii_whitespace()`] returns `true` for:
+    /// space, tab, line feed, form feed and carriage return.
+    pub const WHITESPACE: AsciiSet = AsciiSet::BLANK
+        .with(AsciiChar::LineFeed)
+        .with(AsciiChar::FF)
+        .with(AsciiChar::CarriageReturn);
+
+    /// Creates an empty set.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        AsciiSet::EMPTY
+    }
+
+    /// Creates a set containing the given characters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiSet};
+    /// const SIGN: AsciiSet = AsciiSet::from_chars(&[AsciiChar::Plus, AsciiChar::Minus]);
+    /// assert_eq!(SIGN.len(), 2);
+    /// assert!(SIGN.contains(AsciiChar::Minus));
+    /// ```
+    #[must_use]
+    pub const fn from_chars(chars: &[AsciiChar]) -> Self {
+        let mut set = AsciiSet::EMPTY;
+        let mut chars = chars;
+        while let Some((&ch, rest)) = chars.split_first() {
+            set = set.with(ch);
+            chars = rest;
+        }
+        set
+    }
+
+    /// Creates a set containing all characters from `first` to `last`, inclusive.
+    ///
+    /// The set is empty if `first` comes after `last`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiSet};
+    /// const OCTAL: AsciiSet = AsciiSet::from_range(AsciiChar::_0, AsciiChar::_7);
+    /// assert_eq!(OCTAL.len(), 8);
+    /// assert!(AsciiSet::from_range(AsciiChar::z, AsciiChar::a).is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn from_range(first: AsciiChar, last: AsciiChar) -> Self {
+        AsciiSet {
+            bits: (u128::MAX << first as u8) & (u128::MAX >> (127 - last as u8)),
+        }
+    }
+
+    /// Creates a set from a bitmask, where bit `n` is set if the character with value `n`
+    /// is in the set.
+    #[inline]
+    #[must_use]
+    pub const fn from_bits(bits: u128) -> Self {
+        AsciiSet { bits }
+    }
+
+    /// Returns the bitmask of the set, where bit `n` is set if the character with value `n`
+    /// is in the set.
+    #[inline]
+    #[must_use]
+    pub const fn bits(self) -> u128 {
+        self.bits
+    }
+
+    /// Returns a copy of the set that also contains `ch`.
+    #[inline]
+    #[must_use]
+    pub const fn with(self, ch: AsciiChar) -> Self {
+        AsciiSet {
+            bits: self.bits | (1 << ch as u8),
+        }
+    }
+
+    /// Returns a copy of the set that doesn't contain `ch`.
+    #[inline]
+    #[must_use]
+    pub const fn without(self, ch: AsciiChar) -> Self {
+        AsciiSet {
+            bits: self.bits & !(1 << ch as u8),
+        }
+    }
+
+    /// Checks if `ch` is in the set.
+    #[inline]
+    #[must_use]
+    pub const fn contains(self, ch: AsciiChar) -> bool {
+        self.bits & (1 << ch as u8) != 0
+    }
+
+    /// Returns the set of characters that are in either set.
+    #[inline]
+    #[must_use]
+    pub const fn union(self, other: AsciiSet) -> Self {
+        AsciiSet {
+            bits: self.bits | other.bits,
+        }
+    }
+
+    /// Returns the set of characters that are in both sets.
+    #[inline]
+    #[must_use]
+    pub const fn intersection(self, other: AsciiSet) -> Self {
+        AsciiSet {
+            bits: self.bits & other.bits,
+        }
+    }
+
+    /// Returns the set of characters that are in `self` but not in `other`.
+    #[inline]
+    #[must_use]
+    pub const fn difference(self, other: AsciiSet) -> Self {
+        AsciiSet {
+            bits: self.bits & !other.bits,
+        }
+    }
+
+    /// Returns the set of all ASCII characters that are not in `self`.
+    #[inline]
+    #[must_use]
+    pub const fn complement(self) -> Self {
+        AsciiSet { bits: !self.bits }
+    }
+
+    /// Checks if all characters in `self` are also in `other`.
+    #[inline]
+    #[must_use]
+    pub const fn is_subset(self, other: AsciiSet) -> bool {
+        self.bits & !other.bits == 0
+    }
+
+    /// Returns the number of characters in the set.
+    #[inline]
+    #[must_use]
+    pub const fn len(self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Checks if the set contains no characters.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns an iterator over the characters in the set, in ascending order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiSet};
+    /// let hex_letters = AsciiSet::HEXDIG.difference(AsciiSet::DIGIT | AsciiSet::LOWERCASE);
+    /// let chars: String = hex_letters.iter().map(AsciiChar::as_char).collect();
+    /// assert_eq!(chars, "ABCDEF");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn iter(self) -> AsciiSetIter {
+        AsciiSetIter { bits: self.bits }
+    }
+}
+
+impl BitOr for AsciiSet {
+    type Output = AsciiSet;
+
+    #[inline]
+    fn bitor(self, other: AsciiSet) -> AsciiSet {
+        self.union(other)
+    }
+}
+
+impl BitAnd for AsciiSet {
+    type Output = AsciiSet;
+
+    #[inline]
+    fn bitand(self, other: AsciiSet) -> AsciiSet {
+        self.intersection(other)
+    }
+}
+
+impl Sub for AsciiSet {
+    type Output = AsciiSet;
+
+    #[inline]
+    fn sub(self, other: AsciiSet) -> AsciiSet {
+        self.difference(other)
+    }
+}
+
+impl Not for AsciiSet {
+    type Output = AsciiSet;
+
+    #[inline]
+    fn not(self) -> AsciiSet {
+        self.complement()
+    }
+}
+
+impl fmt::Debug for AsciiSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl From<AsciiChar> for AsciiSet {
+    #[inline]
+    fn from(ch: AsciiChar) -> Self {
+        AsciiSet::EMPTY.with(ch)
+    }
+}
+
+impl<'a> From<&'a [AsciiChar]> for AsciiSet {
+    #[inline]
+    fn from(chars: &[AsciiChar]) -> Self {
+        AsciiSet::from_chars(chars)
+    }
+}
+
+impl FromIterator<AsciiChar> for AsciiSet {
+    fn from_iter<I: IntoIterator<Item = AsciiChar>>(iter: I) -> Self {
+        iter.into_iter().fold(AsciiSet::EMPTY, AsciiSet::with)
+    }
+}
+
+impl Extend<AsciiChar> for AsciiSet {
+    fn extend<I: IntoIterator<Item = AsciiChar>>(&mut self, iter: I) {
+        for ch in iter {
+            *self = self.with(ch);
+        }
+    }
+}
+
+impl IntoIterator for AsciiSet {
+    type Item = AsciiChar;
+    type IntoIter = AsciiSetIter;
+
+    #[inline]
+    fn into_iter(self) -> AsciiSetIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the characters in an [`AsciiSet`], in ascending order.
+#[derive(Clone, Debug)]
+pub struct AsciiSetIter {
+    bits: u128,
+}
+
+impl Iterator for AsciiSetIter {
+    type Item = AsciiChar;
+
+    #[inline]
+    fn next(&mut self) -> Option<AsciiChar> {
+        if self.bits == 0 {
+            return None;
+        }
+        #[allow(clippy::cast_possible_truncation)] // at most 127
+        let value = self.bits.trailing_zeros() as u8;
+        self.bits &= self.bits - 1;
+        // SAFETY: A `u128` has no bit above 127.
+        Some(unsafe { AsciiChar::from_ascii_unchecked(value) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for AsciiSetIter {
+    #[inline]
+    fn next_back(&mut self) -> Option<AsciiChar> {
+        if self.bits == 0 {
+            return None;
+        }
+        #[allow(clippy::cast_possible_truncation)] // at most 127
+        let value = 127 - self.bits.leading_zeros() as u8;
+        self.bits &= !(1 << value);
+        // SAFETY: A `u128` has no bit above 127.
+        Some(unsafe { AsciiChar::from_ascii_unchecked(value) })
+    }
+}
+
+impl ExactSizeIterator for AsciiSetIter {}
+
+#[cfg(test)]
+mod tests {
+    use super::AsciiSet;
+    use ascii_char::AsciiChar;
+    use AsAsciiStr;
+
+    #[test]
+    fn predefined_sets_match_char_methods() {
+        let is_alphabetic: fn(&AsciiChar) -> bool = AsciiChar::is_ascii_alphabetic;
+        let sets = [
+            (AsciiSet::ALPHA, is_alphabetic),
+            (AsciiSet::UPPERCASE, AsciiChar::is_ascii_uppercase),
+            (AsciiSet::LOWERCASE, AsciiChar::is_ascii_lowercase),
+            (AsciiSet::DIGIT, AsciiChar::is_ascii_digit),
+            (AsciiSet::HEXDIG, AsciiChar::is_ascii_hexdigit),
+            (AsciiSet::ALPHANUMERIC, AsciiChar::is_ascii_alphanumeric),
+            (AsciiSet::GRAPHIC, AsciiChar::is_ascii_graphic),
+            (AsciiSet::PRINTABLE, AsciiChar::is_ascii_printable),
+            (AsciiSet::PUNCT, AsciiChar::is_ascii_punctuation),
+            (AsciiSet::CONTROL, AsciiChar::is_ascii_control),
+            (AsciiSet::BLANK, AsciiChar::is_ascii_blank),
+            (AsciiSet::WHITESPACE, AsciiChar::is_ascii_whitespace),
+        ];
+        for &(set, is_member) in &sets {
+            for ch in AsciiSet::ALL {
+                assert_eq!(set.contains(ch), is_member(&ch));
+            }
+        }
+        assert_eq!(AsciiSet::ALL.len(), 128);
+        assert_eq!(AsciiSet::EMPTY.iter().next(), None);
+    }
+
+    #[test]
+    fn algebra() {
+        let abc = AsciiSet::from_chars(&[AsciiChar::a, AsciiChar::b, AsciiChar::c]);
+        assert_eq!(abc, AsciiSet::from_range(AsciiChar::a, AsciiChar::c));
+        assert_eq!(abc.len(), 3);
+        assert_eq!(abc | AsciiSet::LOWERCASE, AsciiSet::LOWERCASE);
+        assert_eq!(abc & AsciiSet::HEXDIG, abc);
+        assert_eq!(
+            abc - AsciiSet::from(AsciiChar::b),
+            abc.without(AsciiChar::b)
+        );
+        assert_eq!(!!abc, abc);
+        assert_eq!((!abc).len(), 125);
+        assert!(abc.is_subset(AsciiSet::ALPHA));
+        assert!(!AsciiSet::ALPHA.is_subset(abc));
+        assert_eq!(
+            AsciiSet::from_range(AsciiChar::Null, AsciiChar::DEL),
+            AsciiSet::ALL
+        );
+        assert_eq!(
+            AsciiSet::from_range(AsciiChar::DEL, AsciiChar::DEL),
+            AsciiSet::from(AsciiChar::DEL)
+        );
+        let collected: AsciiSet = "cabbage".as_ascii_str().unwrap().chars().collect();
+        assert_eq!(collected, abc.with(AsciiChar::g).with(AsciiChar::e));
+    }
+
+    #[test]
+    fn iterate() {
+        let set = AsciiSet::from_chars(&[AsciiChar::DEL, AsciiChar::Null, AsciiChar::A]);
+        let mut iter = set.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(AsciiChar::Null));
+        assert_eq!(iter.next_back(), Some(AsciiChar::DEL));
+        assert_eq!(iter.next_back(), Some(AsciiChar::A));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(AsciiSet::ALL.iter().rev().count(), 128);
+    }
+
+    #[test]
+    fn as_pattern() {
+        let s = "--==[ key_1 ]==--".as_ascii_str().unwrap();
+        assert_eq!(s.trim_matches(AsciiSet::PUNCT), " key_1 ");
+        assert_eq!(s.trim_matches(AsciiSet::PUNCT | AsciiSet::BLANK), "key_1");
+        assert_eq!(s.find(AsciiSet::ALPHANUMERIC), Some(6));
+        assert_eq!(s.rfind(&AsciiSet::ALPHANUMERIC), Some(10));
+        assert_eq!(
+            s.split(!AsciiSet::ALPHANUMERIC)
+                .filter(|part| !part.is_empty())
+                .count(),
+            2
+        );
+        assert_eq!(
+            s.strip_prefix(AsciiSet::from(AsciiChar::Minus)),
+            Some(&s[1..])
+        );
+    }
+}
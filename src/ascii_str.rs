@@ -1,19 +1,30 @@
 #[cfg(feature = "alloc")]
-use alloc::borrow::ToOwned;
+use alloc::borrow::{Cow, ToOwned};
 #[cfg(feature = "alloc")]
 use alloc::boxed::Box;
-use core::{fmt, mem};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
 use core::ops::{Index, IndexMut};
 use core::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 use core::slice::{self, Iter, IterMut, SliceIndex};
+use core::{fmt, mem};
 #[cfg(feature = "std")]
 use std::error::Error;
 #[cfg(feature = "std")]
 use std::ffi::CStr;
 
 use ascii_char::AsciiChar;
+#[cfg(feature = "std")]
+use ascii_cstr::{self, AsciiCString, InteriorNulError};
 #[cfg(feature = "alloc")]
 use ascii_string::AsciiString;
+use encoding::PercentDecode;
+use hex::{self, HexError};
+#[cfg(feature = "alloc")]
+use lossy::LossyStrategy;
+use pattern::{AsciiPattern, DoubleEndedAsciiPattern};
+use validate::ascii_prefix_len;
 
 /// [`AsciiStr`] represents a byte or string slice that only contains ASCII characters.
 ///
@@ -104,6 +115,117 @@ impl AsciiStr {
         AsciiString::from(self.slice.to_vec())
     }
 
+    /// Copies the content of this `AsciiStr` into an owned, NUL-terminated `AsciiCString`.
+    ///
+    /// # Errors
+    /// Returns `Err` with the position of the first [`AsciiChar::Null`] if the string
+    /// contains one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// let name = AsciiStr::from_ascii("eth0").unwrap();
+    /// assert_eq!(name.to_ascii_cstring().unwrap().as_c_str().to_bytes_with_nul(), b"eth0\0");
+    /// let bad = AsciiStr::from_ascii("eth0\0").unwrap();
+    /// assert_eq!(bad.to_ascii_cstring().unwrap_err().nul_position(), 4);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_ascii_cstring(&self) -> Result<AsciiCString, InteriorNulError> {
+        ascii_cstr::to_ascii_cstring(self)
+    }
+
+    /// Decodes pairs of hex digits into bytes.
+    ///
+    /// Both uppercase and lowercase digits are accepted.
+    ///
+    /// # Errors
+    /// Returns an error with the position of the first character that isn't a hex digit,
+    /// or if the string has an odd length.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// let hex = AsciiStr::from_ascii("C0ffee").unwrap();
+    /// assert_eq!(hex.decode_hex(), Ok(vec![0xc0, 0xff, 0xee]));
+    /// let typo = AsciiStr::from_ascii("c0ffe").unwrap();
+    /// assert_eq!(typo.decode_hex().unwrap_err().position(), 5);
+    /// let typo = AsciiStr::from_ascii("c0ffeo").unwrap();
+    /// assert_eq!(typo.decode_hex().unwrap_err().invalid_char(), Some(AsciiChar::o));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decode_hex(&self) -> Result<Vec<u8>, HexError> {
+        let mut bytes = vec![0; self.len() / 2];
+        hex::decode_to_slice(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Decodes pairs of hex digits into the start of `buf`, and returns the written part of `buf`.
+    ///
+    /// This is the variant of [`decode_hex()`](#method.decode_hex) that doesn't allocate.
+    ///
+    /// # Errors
+    /// Returns an error with the position of the first character that isn't a hex digit,
+    /// or if the string has an odd length.
+    /// The content of `buf` is unspecified when an error is returned.
+    ///
+    /// # Panics
+    /// If `buf` is shorter than half the length of this string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// let mut buf = [0; 16];
+    /// let hex = AsciiStr::from_ascii("00ff7F").unwrap();
+    /// assert_eq!(hex.decode_hex_to_slice(&mut buf).unwrap(), [0x00, 0xff, 0x7f]);
+    /// ```
+    pub fn decode_hex_to_slice<'a>(&self, buf: &'a mut [u8]) -> Result<&'a mut [u8], HexError> {
+        hex::decode_to_slice(self, buf)
+    }
+
+    /// Decodes `%XX` escapes into bytes.
+    ///
+    /// A `%` that isn't followed by two hex digits is kept as it is,
+    /// and `+` is not decoded as space; use
+    /// [`percent_decode_iter().plus_as_space()`](crate::encoding::PercentDecode::plus_as_space)
+    /// for `application/x-www-form-urlencoded` data.
+    ///
+    /// Returns `Cow::Borrowed` if the string contains no `%`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// use std::borrow::Cow;
+    /// let encoded = AsciiStr::from_ascii("caf%C3%A9%20au%20lait").unwrap();
+    /// assert_eq!(encoded.percent_decode(), "café au lait".as_bytes());
+    /// let plain = AsciiStr::from_ascii("100+1").unwrap();
+    /// assert!(matches!(plain.percent_decode(), Cow::Borrowed(b"100+1")));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn percent_decode(&self) -> Cow<'_, [u8]> {
+        let decoder = self.percent_decode_iter();
+        if decoder.is_identity() {
+            Cow::Borrowed(self.as_bytes())
+        } else {
+            Cow::Owned(decoder.collect())
+        }
+    }
+
+    /// Returns an iterator over the bytes of this percent-encoded string,
+    /// which is the variant of [`percent_decode()`](#method.percent_decode) that doesn't
+    /// allocate.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// let encoded = AsciiStr::from_ascii("a%2Fb+c").unwrap();
+    /// assert!(encoded.percent_decode_iter().eq(b"a/b+c".iter().copied()));
+    /// ```
+    #[inline]
+    pub fn percent_decode_iter(&self) -> PercentDecode<'_> {
+        PercentDecode::new(self)
+    }
+
     /// Converts anything that can represent a byte slice into an `AsciiStr`.
     ///
     /// # Errors
@@ -149,7 +271,7 @@ impl AsciiStr {
             } else if b[valid].is_ascii() {
                 valid += 1;
             } else {
-                return Err(AsAsciiStrError(valid));
+                return Err(AsAsciiStrError::new(b, valid));
             }
         }
     }
@@ -172,6 +294,52 @@ impl AsciiStr {
         Self::from_ascii_bytes(s.as_bytes())
     }
 
+    /// Converts a byte slice into an `AsciiStr`, replacing every non-ASCII byte with
+    /// `replacement`.
+    ///
+    /// This only allocates if there are non-ASCII bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// use std::borrow::Cow;
+    /// let clean = AsciiStr::from_ascii_lossy("plain", AsciiChar::Question);
+    /// assert!(matches!(clean, Cow::Borrowed(_)));
+    /// let dirty = AsciiStr::from_ascii_lossy("na\u{ef}ve", AsciiChar::Question);
+    /// assert_eq!(dirty, Cow::<AsciiStr>::Owned("na??ve".parse().unwrap()));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn from_ascii_lossy<B>(bytes: &B, replacement: AsciiChar) -> Cow<'_, AsciiStr>
+    where
+        B: AsRef<[u8]> + ?Sized,
+    {
+        AsciiStr::from_ascii_lossy_with(bytes, LossyStrategy::Replace(replacement))
+    }
+
+    /// Converts a byte slice into an `AsciiStr`, handling non-ASCII bytes as described
+    /// by `strategy`.
+    ///
+    /// This only allocates if there are non-ASCII bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiStr, LossyStrategy};
+    /// let s = AsciiStr::from_ascii_lossy_with(b"\x1b[0m\x9b", LossyStrategy::CaretEscape);
+    /// assert_eq!(&*s, "\x1b[0mM-^[");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn from_ascii_lossy_with<B>(bytes: &B, strategy: LossyStrategy) -> Cow<'_, AsciiStr>
+    where
+        B: AsRef<[u8]> + ?Sized,
+    {
+        match bytes.as_ref().as_ascii_str() {
+            Ok(ascii) => Cow::Borrowed(ascii),
+            Err(_) => Cow::Owned(AsciiString::from_ascii_lossy_with(bytes, strategy)),
+        }
+    }
+
     /// Converts anything that can be represented as a byte slice to an `AsciiStr` without checking
     /// for non-ASCII characters..
     ///
@@ -238,7 +406,11 @@ impl AsciiStr {
         CharsMut(self.slice.iter_mut())
     }
 
-    /// Returns an iterator over parts of the `AsciiStr` separated by a character.
+    /// Returns an iterator over parts of the `AsciiStr` separated by matches of a pattern.
+    ///
+    /// The pattern can be any [`AsciiPattern`], such as an [`AsciiChar`], a substring or a
+    /// predicate. If the pattern matches only single characters, the iterator is
+    /// double-ended.
     ///
     /// # Examples
     /// ```
@@ -248,13 +420,251 @@ impl AsciiStr {
     ///     .map(|a| a.as_str())
     ///     .collect::<Vec<_>>();
     /// assert_eq!(words, ["apple", "banana", "lemon"]);
+    ///
+    /// let fields = AsciiStr::from_ascii("a::b::c").unwrap()
+    ///     .split(AsciiStr::from_ascii("::").unwrap())
+    ///     .map(|a| a.as_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(fields, ["a", "b", "c"]);
+    /// ```
+    #[must_use]
+    pub fn split<P: AsciiPattern>(&self, pat: P) -> Split<'_, P> {
+        Split(SplitInternal::new(self, pat, true))
+    }
+
+    /// Returns an iterator over parts of the `AsciiStr` separated by matches of a pattern,
+    /// starting from the end.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiStr, AsciiChar};
+    /// let parts = AsciiStr::from_ascii("www.example.com").unwrap()
+    ///     .rsplit(AsciiChar::Dot)
+    ///     .map(|a| a.as_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(parts, ["com", "example", "www"]);
+    /// ```
+    #[must_use]
+    pub fn rsplit<P: AsciiPattern>(&self, pat: P) -> RSplit<'_, P> {
+        RSplit(SplitInternal::new(self, pat, true))
+    }
+
+    /// Returns an iterator over at most `n` parts of the `AsciiStr` separated by matches of a
+    /// pattern.
+    ///
+    /// The last part contains the remainder of the string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiStr, AsciiChar};
+    /// let parts = AsciiStr::from_ascii("key=value=with=equals").unwrap()
+    ///     .splitn(2, AsciiChar::Equal)
+    ///     .map(|a| a.as_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(parts, ["key", "value=with=equals"]);
+    /// ```
+    #[must_use]
+    pub fn splitn<P: AsciiPattern>(&self, n: usize, pat: P) -> SplitN<'_, P> {
+        SplitN(SplitNInternal {
+            iter: SplitInternal::new(self, pat, true),
+            count: n,
+        })
+    }
+
+    /// Returns an iterator over at most `n` parts of the `AsciiStr` separated by matches of a
+    /// pattern, starting from the end.
+    ///
+    /// The last part contains the remainder of the string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiStr, AsciiChar};
+    /// let parts = AsciiStr::from_ascii("/usr/local/bin").unwrap()
+    ///     .rsplitn(2, AsciiChar::Slash)
+    ///     .map(|a| a.as_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(parts, ["bin", "/usr/local"]);
+    /// ```
+    #[must_use]
+    pub fn rsplitn<P: AsciiPattern>(&self, n: usize, pat: P) -> RSplitN<'_, P> {
+        RSplitN(SplitNInternal {
+            iter: SplitInternal::new(self, pat, true),
+            count: n,
+        })
+    }
+
+    /// Returns an iterator over parts of the `AsciiStr` which are terminated by matches of a
+    /// pattern.
+    ///
+    /// This is equivalent to [`split()`](#method.split), except that a trailing empty part is
+    /// skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiStr, AsciiChar};
+    /// let parts = AsciiStr::from_ascii("A;B;C;").unwrap()
+    ///     .split_terminator(AsciiChar::Semicolon)
+    ///     .map(|a| a.as_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(parts, ["A", "B", "C"]);
+    /// ```
+    #[must_use]
+    pub fn split_terminator<P: AsciiPattern>(&self, pat: P) -> SplitTerminator<'_, P> {
+        SplitTerminator(SplitInternal::new(self, pat, false))
+    }
+
+    /// Returns an iterator over parts of the `AsciiStr` which are terminated by matches of a
+    /// pattern, starting from the end.
+    ///
+    /// This is equivalent to [`rsplit()`](#method.rsplit), except that a trailing empty part
+    /// is skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiStr, AsciiChar};
+    /// let parts = AsciiStr::from_ascii("A;B;C;").unwrap()
+    ///     .rsplit_terminator(AsciiChar::Semicolon)
+    ///     .map(|a| a.as_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(parts, ["C", "B", "A"]);
+    /// ```
+    #[must_use]
+    pub fn rsplit_terminator<P: AsciiPattern>(&self, pat: P) -> RSplitTerminator<'_, P> {
+        RSplitTerminator(SplitInternal::new(self, pat, false))
+    }
+
+    /// Returns an iterator over parts of the `AsciiStr` which end with a match of a pattern.
+    ///
+    /// Unlike [`split()`](#method.split), the matched part is included at the end of each
+    /// part. If the last part of the string is empty, it is skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiStr, AsciiChar};
+    /// let lines = AsciiStr::from_ascii("one\ntwo\nthree").unwrap()
+    ///     .split_inclusive(AsciiChar::LineFeed)
+    ///     .map(|a| a.as_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(lines, ["one\n", "two\n", "three"]);
+    /// ```
+    #[must_use]
+    pub fn split_inclusive<P: AsciiPattern>(&self, pat: P) -> SplitInclusive<'_, P> {
+        SplitInclusive(SplitInternal::new(self, pat, false))
+    }
+
+    /// Returns an iterator over the non-empty parts of the `AsciiStr` separated by whitespace.
+    ///
+    /// Whitespace is determined by [`AsciiChar::is_whitespace()`], the same as for
+    /// [`trim()`](#method.trim).
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// let words = AsciiStr::from_ascii(" MAIL  FROM:\t<a@b.c>\r\n").unwrap()
+    ///     .split_whitespace()
+    ///     .map(|a| a.as_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(words, ["MAIL", "FROM:", "<a@b.c>"]);
+    /// ```
+    #[must_use]
+    pub fn split_whitespace(&self) -> SplitWhitespace<'_> {
+        SplitWhitespace {
+            inner: self.split(AsciiChar::is_whitespace),
+        }
+    }
+
+    /// Returns an iterator over the non-empty parts of the `AsciiStr` separated by ASCII
+    /// whitespace.
+    ///
+    /// Whitespace is determined by [`AsciiChar::is_ascii_whitespace()`], which unlike
+    /// [`split_whitespace()`](#method.split_whitespace) doesn't split on vertical tab.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// let words = AsciiStr::from_ascii("a\x0bb c").unwrap()
+    ///     .split_ascii_whitespace()
+    ///     .map(|a| a.as_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(words, ["a\x0bb", "c"]);
+    /// ```
+    #[must_use]
+    pub fn split_ascii_whitespace(&self) -> SplitWhitespace<'_> {
+        SplitWhitespace {
+            inner: self.split(|ch: AsciiChar| ch.is_ascii_whitespace()),
+        }
+    }
+
+    /// Returns an iterator over the non-empty parts of the `AsciiStr` separated by spaces and
+    /// horizontal tabs.
+    ///
+    /// Blanks are determined by [`AsciiChar::is_ascii_blank()`], so line breaks are kept
+    /// inside the parts.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// let words = AsciiStr::from_ascii("250  OK\t\r\n").unwrap()
+    ///     .split_ascii_blank()
+    ///     .map(|a| a.as_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(words, ["250", "OK", "\r\n"]);
+    /// ```
+    #[must_use]
+    pub fn split_ascii_blank(&self) -> SplitWhitespace<'_> {
+        SplitWhitespace {
+            inner: self.split(|ch: AsciiChar| ch.is_ascii_blank()),
+        }
+    }
+
+    /// Splits the string on the first match of a pattern, and returns the parts before and
+    /// after the match.
+    ///
+    /// Returns `None` if the pattern doesn't match.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiStr, AsciiChar};
+    /// let header = AsciiStr::from_ascii("Host: example.com:8080").unwrap();
+    /// let (name, value) = header.split_once(AsciiChar::Colon).unwrap();
+    /// assert_eq!(name, "Host");
+    /// assert_eq!(value, " example.com:8080");
+    /// assert_eq!(header.split_once(AsciiChar::Semicolon), None);
+    /// ```
+    #[must_use]
+    pub fn split_once<P: AsciiPattern>(&self, mut pat: P) -> Option<(&AsciiStr, &AsciiStr)> {
+        let (start, end) = pat.find_in(self)?;
+        // SAFETY: The pattern returns a range within `self`.
+        unsafe {
+            Some((
+                self.slice.get_unchecked(..start).into(),
+                self.slice.get_unchecked(end..).into(),
+            ))
+        }
+    }
+
+    /// Splits the string on the last match of a pattern, and returns the parts before and
+    /// after the match.
+    ///
+    /// Returns `None` if the pattern doesn't match.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiStr, AsciiChar};
+    /// let address = AsciiStr::from_ascii("example.com:8080").unwrap();
+    /// let (host, port) = address.rsplit_once(AsciiChar::Colon).unwrap();
+    /// assert_eq!(host, "example.com");
+    /// assert_eq!(port, "8080");
     /// ```
     #[must_use]
-    pub fn split(&self, on: AsciiChar) -> impl DoubleEndedIterator<Item = &AsciiStr> {
-        Split {
-            on,
-            ended: false,
-            chars: self.chars(),
+    pub fn rsplit_once<P: AsciiPattern>(&self, mut pat: P) -> Option<(&AsciiStr, &AsciiStr)> {
+        let (start, end) = pat.rfind_in(self)?;
+        // SAFETY: The pattern returns a range within `self`.
+        unsafe {
+            Some((
+                self.slice.get_unchecked(..start).into(),
+                self.slice.get_unchecked(end..).into(),
+            ))
         }
     }
 
@@ -269,6 +679,113 @@ impl AsciiStr {
         Lines { string: self }
     }
 
+    /// Returns the byte index of the first match of `pat`, or `None` if it doesn't match.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// let s = AsciiStr::from_ascii("Content-Length: 42").unwrap();
+    /// assert_eq!(s.find(AsciiChar::Colon), Some(14));
+    /// assert_eq!(s.find(AsciiChar::is_whitespace), Some(15));
+    /// assert_eq!(s.find(AsciiChar::Semicolon), None);
+    /// ```
+    #[must_use]
+    pub fn find<P: AsciiPattern>(&self, mut pat: P) -> Option<usize> {
+        pat.find_in(self).map(|(start, _)| start)
+    }
+
+    /// Returns the byte index of the last match of `pat`, or `None` if it doesn't match.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// let s = AsciiStr::from_ascii("a.b.c").unwrap();
+    /// assert_eq!(s.rfind(AsciiChar::Dot), Some(3));
+    /// assert_eq!(s.rfind(AsciiStr::from_ascii("a.").unwrap()), Some(0));
+    /// ```
+    #[must_use]
+    pub fn rfind<P: AsciiPattern>(&self, mut pat: P) -> Option<usize> {
+        pat.rfind_in(self).map(|(start, _)| start)
+    }
+
+    /// Returns `true` if `pat` matches somewhere in this string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// let s = AsciiStr::from_ascii("text/html; charset=utf-8").unwrap();
+    /// assert!(s.contains(AsciiStr::from_ascii("charset").unwrap()));
+    /// assert!(s.contains(AsciiChar::Semicolon));
+    /// assert!(!s.contains(AsciiChar::is_uppercase));
+    /// ```
+    #[must_use]
+    pub fn contains<P: AsciiPattern>(&self, mut pat: P) -> bool {
+        pat.find_in(self).is_some()
+    }
+
+    /// Returns an iterator over the non-overlapping matches of `pat`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// let s = AsciiStr::from_ascii("1a22b333").unwrap();
+    /// let digits = s.matches(|ch: AsciiChar| ch.is_ascii_digit()).count();
+    /// assert_eq!(digits, 6);
+    /// ```
+    #[must_use]
+    pub fn matches<P: AsciiPattern>(&self, pat: P) -> Matches<'_, P> {
+        Matches(MatchIndicesInternal::new(self, pat))
+    }
+
+    /// Returns an iterator over the non-overlapping matches of `pat`, starting from the end.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// let s = AsciiStr::from_ascii("abXXcdXX").unwrap();
+    /// let xs = AsciiStr::from_ascii("XX").unwrap();
+    /// assert_eq!(s.rmatches(xs).count(), 2);
+    /// ```
+    #[must_use]
+    pub fn rmatches<P: AsciiPattern>(&self, pat: P) -> RMatches<'_, P> {
+        RMatches(MatchIndicesInternal::new(self, pat))
+    }
+
+    /// Returns an iterator over the non-overlapping matches of `pat` and the byte index they
+    /// start at.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// let s = AsciiStr::from_ascii("abcXXXabcYYYabc").unwrap();
+    /// let abc = AsciiStr::from_ascii("abc").unwrap();
+    /// let indices = s.match_indices(abc).map(|(i, _)| i).collect::<Vec<_>>();
+    /// assert_eq!(indices, [0, 6, 12]);
+    /// ```
+    #[must_use]
+    pub fn match_indices<P: AsciiPattern>(&self, pat: P) -> MatchIndices<'_, P> {
+        MatchIndices(MatchIndicesInternal::new(self, pat))
+    }
+
+    /// Returns an iterator over the non-overlapping matches of `pat` and the byte index they
+    /// start at, starting from the end.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// let s = AsciiStr::from_ascii("aaaa").unwrap();
+    /// let aa = AsciiStr::from_ascii("aa").unwrap();
+    /// let rev = s.rmatch_indices(aa).map(|(i, _)| i).collect::<Vec<_>>();
+    /// assert_eq!(rev, [2, 0]);
+    /// let s = AsciiStr::from_ascii("aaa").unwrap();
+    /// let rev = s.rmatch_indices(aa).map(|(i, _)| i).collect::<Vec<_>>();
+    /// assert_eq!(rev, [1]);
+    /// ```
+    #[must_use]
+    pub fn rmatch_indices<P: AsciiPattern>(&self, pat: P) -> RMatchIndices<'_, P> {
+        RMatchIndices(MatchIndicesInternal::new(self, pat))
+    }
+
     /// Returns an ASCII string slice with leading and trailing whitespace removed.
     ///
     /// # Examples
@@ -303,35 +820,337 @@ impl AsciiStr {
         AsciiStr::new(trimmed)
     }
 
-    /// Returns an ASCII string slice with trailing whitespace removed.
+    /// Returns an ASCII string slice with trailing whitespace removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// let example = AsciiStr::from_ascii("  \twhite \tspace  \t").unwrap();
+    /// assert_eq!("  \twhite \tspace", example.trim_end());
+    /// ```
+    #[must_use]
+    pub const fn trim_end(&self) -> &Self {
+        let mut trimmed = &self.slice;
+        while let Some((last, rest)) = trimmed.split_last() {
+            if last.is_whitespace() {
+                trimmed = rest;
+            } else {
+                break;
+            }
+        }
+        AsciiStr::new(trimmed)
+    }
+
+    /// Returns `true` if the string starts with a match of `pat`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// let s = AsciiStr::from_ascii("Content-Type").unwrap();
+    /// assert!(s.starts_with(AsciiStr::from_ascii("Content-").unwrap()));
+    /// assert!(s.starts_with(AsciiChar::is_uppercase));
+    /// assert!(!s.starts_with(AsciiChar::c));
+    /// ```
+    #[must_use]
+    pub fn starts_with<P: AsciiPattern>(&self, mut pat: P) -> bool {
+        pat.prefix_len_in(self).is_some()
+    }
+
+    /// Returns `true` if the string ends with a match of `pat`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// let s = AsciiStr::from_ascii("index.html").unwrap();
+    /// assert!(s.ends_with(AsciiStr::from_ascii(".html").unwrap()));
+    /// assert!(!s.ends_with(AsciiChar::is_uppercase));
+    /// ```
+    #[must_use]
+    pub fn ends_with<P: AsciiPattern>(&self, mut pat: P) -> bool {
+        pat.suffix_len_in(self).is_some()
+    }
+
+    /// Returns the string with a match of `pat` removed from the start,
+    /// or `None` if it doesn't start with one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// let s = AsciiStr::from_ascii("Bearer abc123").unwrap();
+    /// let token = s.strip_prefix(AsciiStr::from_ascii("Bearer ").unwrap());
+    /// assert_eq!(token.map(AsciiStr::as_str), Some("abc123"));
+    /// assert_eq!(s.strip_prefix(AsciiChar::Space), None);
+    /// ```
+    #[must_use]
+    pub fn strip_prefix<P: AsciiPattern>(&self, mut pat: P) -> Option<&AsciiStr> {
+        let len = pat.prefix_len_in(self)?;
+        // SAFETY: The pattern returns the length of a match within `self`.
+        Some(unsafe { self.slice.get_unchecked(len..) }.into())
+    }
+
+    /// Returns the string with a match of `pat` removed from the end,
+    /// or `None` if it doesn't end with one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// let s = AsciiStr::from_ascii("250 OK\r\n").unwrap();
+    /// let line = s.strip_suffix(AsciiStr::from_ascii("\r\n").unwrap());
+    /// assert_eq!(line.map(AsciiStr::as_str), Some("250 OK"));
+    /// assert_eq!(s.strip_suffix(AsciiChar::CarriageReturn), None);
+    /// ```
+    #[must_use]
+    pub fn strip_suffix<P: AsciiPattern>(&self, mut pat: P) -> Option<&AsciiStr> {
+        let len = pat.suffix_len_in(self)?;
+        // SAFETY: The pattern returns the length of a match within `self`.
+        Some(unsafe { self.slice.get_unchecked(..self.len() - len) }.into())
+    }
+
+    /// Returns the string with all leading and trailing matches of `pat` removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// let s = AsciiStr::from_ascii("\"quoted\"").unwrap();
+    /// assert_eq!(s.trim_matches(AsciiChar::Quotation), "quoted");
+    /// let s = AsciiStr::from_ascii("123abc456").unwrap();
+    /// assert_eq!(s.trim_matches(|ch: AsciiChar| ch.is_ascii_digit()), "abc");
+    /// ```
+    #[must_use]
+    pub fn trim_matches<P: DoubleEndedAsciiPattern>(&self, mut pat: P) -> &AsciiStr {
+        let mut trimmed = self;
+        while let Some(len) = pat.prefix_len_in(trimmed).filter(|&len| len > 0) {
+            // SAFETY: The pattern returns the length of a match within `trimmed`.
+            trimmed = unsafe { trimmed.slice.get_unchecked(len..) }.into();
+        }
+        while let Some(len) = pat.suffix_len_in(trimmed).filter(|&len| len > 0) {
+            // SAFETY: The pattern returns the length of a match within `trimmed`.
+            trimmed = unsafe { trimmed.slice.get_unchecked(..trimmed.len() - len) }.into();
+        }
+        trimmed
+    }
+
+    /// Returns the string with all leading matches of `pat` removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// let s = AsciiStr::from_ascii("0000420").unwrap();
+    /// assert_eq!(s.trim_start_matches(AsciiChar::_0), "420");
+    /// let s = AsciiStr::from_ascii("../../etc").unwrap();
+    /// assert_eq!(s.trim_start_matches(AsciiStr::from_ascii("../").unwrap()), "etc");
+    /// ```
+    #[must_use]
+    pub fn trim_start_matches<P: AsciiPattern>(&self, mut pat: P) -> &AsciiStr {
+        let mut trimmed = self;
+        while let Some(len) = pat.prefix_len_in(trimmed).filter(|&len| len > 0) {
+            // SAFETY: The pattern returns the length of a match within `trimmed`.
+            trimmed = unsafe { trimmed.slice.get_unchecked(len..) }.into();
+        }
+        trimmed
+    }
+
+    /// Returns the string with all trailing matches of `pat` removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// let s = AsciiStr::from_ascii("1.500").unwrap();
+    /// assert_eq!(s.trim_end_matches(AsciiChar::_0), "1.5");
+    /// ```
+    #[must_use]
+    pub fn trim_end_matches<P: AsciiPattern>(&self, mut pat: P) -> &AsciiStr {
+        let mut trimmed = self;
+        while let Some(len) = pat.suffix_len_in(trimmed).filter(|&len| len > 0) {
+            // SAFETY: The pattern returns the length of a match within `trimmed`.
+            trimmed = unsafe { trimmed.slice.get_unchecked(..trimmed.len() - len) }.into();
+        }
+        trimmed
+    }
+
+    /// Returns `true` if the string starts with `prefix`.
+    ///
+    /// This is a `const fn` version of [`starts_with()`](#method.starts_with)
+    /// that only supports substrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[macro_use] extern crate ascii;
+    /// # use ascii::AsciiStr;
+    /// # fn main() {
+    /// const HEADER: &AsciiStr = ascii!("X-Request-Id");
+    /// const IS_EXTENSION: bool = HEADER.starts_with_ascii_str(ascii!("X-"));
+    /// assert!(IS_EXTENSION);
+    /// # }
+    /// ```
+    #[must_use]
+    pub const fn starts_with_ascii_str(&self, prefix: &AsciiStr) -> bool {
+        self.strip_prefix_ascii_str(prefix).is_some()
+    }
+
+    /// Returns `true` if the string ends with `suffix`.
+    ///
+    /// This is a `const fn` version of [`ends_with()`](#method.ends_with)
+    /// that only supports substrings.
+    #[must_use]
+    pub const fn ends_with_ascii_str(&self, suffix: &AsciiStr) -> bool {
+        self.strip_suffix_ascii_str(suffix).is_some()
+    }
+
+    /// Returns the string with `prefix` removed from the start,
+    /// or `None` if it doesn't start with it.
+    ///
+    /// This is a `const fn` version of [`strip_prefix()`](#method.strip_prefix)
+    /// that only supports substrings.
+    #[must_use]
+    pub const fn strip_prefix_ascii_str(&self, prefix: &AsciiStr) -> Option<&AsciiStr> {
+        let mut rest = &self.slice;
+        let mut prefix = &prefix.slice;
+        while let Some((expected, prefix_rest)) = prefix.split_first() {
+            match rest.split_first() {
+                Some((ch, rest_rest)) if *ch as u8 == *expected as u8 => {
+                    rest = rest_rest;
+                    prefix = prefix_rest;
+                }
+                _ => return None,
+            }
+        }
+        Some(AsciiStr::new(rest))
+    }
+
+    /// Returns the string with `suffix` removed from the end,
+    /// or `None` if it doesn't end with it.
+    ///
+    /// This is a `const fn` version of [`strip_suffix()`](#method.strip_suffix)
+    /// that only supports substrings.
+    #[must_use]
+    pub const fn strip_suffix_ascii_str(&self, suffix: &AsciiStr) -> Option<&AsciiStr> {
+        let mut rest = &self.slice;
+        let mut suffix = &suffix.slice;
+        while let Some((expected, suffix_rest)) = suffix.split_last() {
+            match rest.split_last() {
+                Some((ch, rest_rest)) if *ch as u8 == *expected as u8 => {
+                    rest = rest_rest;
+                    suffix = suffix_rest;
+                }
+                _ => return None,
+            }
+        }
+        Some(AsciiStr::new(rest))
+    }
+
+    /// Compares two strings case-insensitively.
+    #[must_use]
+    pub fn eq_ignore_ascii_case(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .chars()
+                .zip(other.chars())
+                .all(|(ch, other_ch)| ch.eq_ignore_ascii_case(&other_ch))
+    }
+
+    /// Compares two strings case-insensitively, ordering them as if both were lowercase.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// # use std::cmp::Ordering;
+    /// let a = AsciiStr::from_ascii("Content-Length").unwrap();
+    /// let b = AsciiStr::from_ascii("content-type").unwrap();
+    /// assert_eq!(a.cmp_ignore_ascii_case(b), Ordering::Less);
+    /// let upper = AsciiStr::from_ascii("CONTENT-LENGTH").unwrap();
+    /// assert_eq!(a.cmp_ignore_ascii_case(upper), Ordering::Equal);
+    /// // `_` sorts between uppercase and lowercase letters
+    /// let underscore = AsciiStr::from_ascii("_").unwrap();
+    /// assert_eq!(underscore.cmp_ignore_ascii_case(a), Ordering::Less);
+    /// ```
+    #[must_use]
+    pub fn cmp_ignore_ascii_case(&self, other: &Self) -> Ordering {
+        self.chars()
+            .map(|ch| ch.to_ascii_lowercase())
+            .cmp(other.chars().map(|ch| ch.to_ascii_lowercase()))
+    }
+
+    /// Returns the index of the first case-insensitive match of `needle`.
+    ///
+    /// An empty needle matches at index 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// let s = AsciiStr::from_ascii("multipart/form-data; BOUNDARY=xyz").unwrap();
+    /// let needle = AsciiStr::from_ascii("boundary=").unwrap();
+    /// assert_eq!(s.find_ignore_ascii_case(needle), Some(21));
+    /// ```
+    #[must_use]
+    pub fn find_ignore_ascii_case(&self, needle: &AsciiStr) -> Option<usize> {
+        if needle.is_empty() {
+            return Some(0);
+        }
+        self.slice
+            .windows(needle.len())
+            .position(|window| AsciiStr::new(window).eq_ignore_ascii_case(needle))
+    }
+
+    /// Returns `true` if the string starts with `prefix`, ignoring case.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// let s = AsciiStr::from_ascii("CONTENT-TYPE").unwrap();
+    /// assert!(s.starts_with_ignore_ascii_case(AsciiStr::from_ascii("Content-").unwrap()));
+    /// ```
+    #[must_use]
+    pub fn starts_with_ignore_ascii_case(&self, prefix: &AsciiStr) -> bool {
+        self.strip_prefix_ignore_ascii_case(prefix).is_some()
+    }
+
+    /// Returns `true` if the string ends with `suffix`, ignoring case.
     ///
     /// # Examples
     /// ```
     /// # use ascii::AsciiStr;
-    /// let example = AsciiStr::from_ascii("  \twhite \tspace  \t").unwrap();
-    /// assert_eq!("  \twhite \tspace", example.trim_end());
+    /// let s = AsciiStr::from_ascii("mail.Example.COM").unwrap();
+    /// assert!(s.ends_with_ignore_ascii_case(AsciiStr::from_ascii(".com").unwrap()));
     /// ```
     #[must_use]
-    pub const fn trim_end(&self) -> &Self {
-        let mut trimmed = &self.slice;
-        while let Some((last, rest)) = trimmed.split_last() {
-            if last.is_whitespace() {
-                trimmed = rest;
-            } else {
-                break;
-            }
+    pub fn ends_with_ignore_ascii_case(&self, suffix: &AsciiStr) -> bool {
+        match self.len().checked_sub(suffix.len()) {
+            // SAFETY: `start` is at most `self.len()`.
+            Some(start) => AsciiStr::new(unsafe { self.slice.get_unchecked(start..) })
+                .eq_ignore_ascii_case(suffix),
+            None => false,
         }
-        AsciiStr::new(trimmed)
     }
 
-    /// Compares two strings case-insensitively.
+    /// Returns the string with `prefix` removed from the start if it starts with it,
+    /// ignoring case.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// let line = AsciiStr::from_ascii("mail from:<alice@example.com>").unwrap();
+    /// let command = AsciiStr::from_ascii("MAIL FROM:").unwrap();
+    /// let path = line.strip_prefix_ignore_ascii_case(command);
+    /// assert_eq!(path.map(AsciiStr::as_str), Some("<alice@example.com>"));
+    /// ```
     #[must_use]
-    pub fn eq_ignore_ascii_case(&self, other: &Self) -> bool {
-        self.len() == other.len()
-            && self
-                .chars()
-                .zip(other.chars())
-                .all(|(ch, other_ch)| ch.eq_ignore_ascii_case(&other_ch))
+    pub fn strip_prefix_ignore_ascii_case(&self, prefix: &AsciiStr) -> Option<&AsciiStr> {
+        if prefix.len() > self.len() {
+            return None;
+        }
+        // SAFETY: `prefix.len()` is at most `self.len()`.
+        let (start, rest) = unsafe {
+            (
+                self.slice.get_unchecked(..prefix.len()),
+                self.slice.get_unchecked(prefix.len()..),
+            )
+        };
+        if AsciiStr::new(start).eq_ignore_ascii_case(prefix) {
+            Some(AsciiStr::new(rest))
+        } else {
+            None
+        }
     }
 
     /// Replaces lowercase letters with their uppercase equivalent.
@@ -688,197 +1507,736 @@ impl<'a> DoubleEndedIterator for CharsMut<'a> {
         self.0.next_back()
     }
 }
-impl<'a> ExactSizeIterator for CharsMut<'a> {
-    fn len(&self) -> usize {
-        self.0.len()
-    }
+impl<'a> ExactSizeIterator for CharsMut<'a> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// An immutable iterator over the characters of an `AsciiStr`.
+#[derive(Clone, Debug)]
+pub struct CharsRef<'a>(Iter<'a, AsciiChar>);
+impl<'a> CharsRef<'a> {
+    /// Returns the ascii string slice with the remaining characters.
+    #[must_use]
+    pub fn as_str(&self) -> &'a AsciiStr {
+        self.0.as_slice().into()
+    }
+}
+impl<'a> Iterator for CharsRef<'a> {
+    type Item = &'a AsciiChar;
+    #[inline]
+    fn next(&mut self) -> Option<&'a AsciiChar> {
+        self.0.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+impl<'a> DoubleEndedIterator for CharsRef<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a AsciiChar> {
+        self.0.next_back()
+    }
+}
+
+/// An iterator over the lines of the internal character array.
+#[derive(Clone, Debug)]
+struct Lines<'a> {
+    string: &'a AsciiStr,
+}
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a AsciiStr;
+
+    fn next(&mut self) -> Option<&'a AsciiStr> {
+        if let Some(idx) = self
+            .string
+            .chars()
+            .position(|chr| chr == AsciiChar::LineFeed)
+        {
+            // SAFETY: `idx` is guaranteed to be `1..len`, as we get it from `position` as `0..len` and make sure it's not `0`.
+            let line = if idx > 0
+                && *unsafe { self.string.as_slice().get_unchecked(idx - 1) }
+                    == AsciiChar::CarriageReturn
+            {
+                // SAFETY: As per above, `idx` is guaranteed to be `1..len`
+                unsafe { self.string.as_slice().get_unchecked(..idx - 1).into() }
+            } else {
+                // SAFETY: As per above, `idx` is guaranteed to be `0..len`
+                unsafe { self.string.as_slice().get_unchecked(..idx).into() }
+            };
+            // SAFETY: As per above, `idx` is guaranteed to be `0..len`, so at the extreme, slicing `len..` is a valid empty slice.
+            self.string = unsafe { self.string.as_slice().get_unchecked(idx + 1..).into() };
+            Some(line)
+        } else if self.string.is_empty() {
+            None
+        } else {
+            let line = self.string;
+            // SAFETY: An empty string is a valid string.
+            self.string = unsafe { AsciiStr::from_ascii_unchecked(b"") };
+            Some(line)
+        }
+    }
+}
+
+impl<'a> DoubleEndedIterator for Lines<'a> {
+    fn next_back(&mut self) -> Option<&'a AsciiStr> {
+        if self.string.is_empty() {
+            return None;
+        }
+
+        // If we end with `LF` / `CR/LF`, remove them
+        if let Some(AsciiChar::LineFeed) = self.string.last() {
+            // SAFETY: `last()` returned `Some`, so our len is at least 1.
+            self.string = unsafe {
+                self.string
+                    .as_slice()
+                    .get_unchecked(..self.string.len() - 1)
+                    .into()
+            };
+
+            if let Some(AsciiChar::CarriageReturn) = self.string.last() {
+                // SAFETY: `last()` returned `Some`, so our len is at least 1.
+                self.string = unsafe {
+                    self.string
+                        .as_slice()
+                        .get_unchecked(..self.string.len() - 1)
+                        .into()
+                };
+            }
+        }
+
+        // Get the position of the first `LF` from the end.
+        let lf_rev_pos = self
+            .string
+            .chars()
+            .rev()
+            .position(|ch| ch == AsciiChar::LineFeed)
+            .unwrap_or_else(|| self.string.len());
+
+        // SAFETY: `lf_rev_pos` will be in range `0..=len`, so `len - lf_rev_pos`
+        //         will be within `0..=len`, making it correct as a start and end
+        //         point for the strings.
+        let line = unsafe {
+            self.string
+                .as_slice()
+                .get_unchecked(self.string.len() - lf_rev_pos..)
+                .into()
+        };
+        self.string = unsafe {
+            self.string
+                .as_slice()
+                .get_unchecked(..self.string.len() - lf_rev_pos)
+                .into()
+        };
+        Some(line)
+    }
+}
+
+/// The state shared by the `split` family of iterators.
+///
+/// This mirrors the implementation of `str`'s split iterators, with the difference that the
+/// pattern is searched for in the part of the string that hasn't been yielded yet instead of
+/// being driven by a stateful searcher.
+#[derive(Clone, Debug)]
+struct SplitInternal<'a, P> {
+    haystack: &'a AsciiStr,
+    /// The start of the next part from the front.
+    start: usize,
+    /// The end of the next part from the back.
+    end: usize,
+    /// Where searching from the front resumes.
+    ///
+    /// This is past `start` after an empty match, and can be `end + 1`.
+    position: usize,
+    /// Where searching from the back resumes, or `None` if an empty match was found at `start`.
+    back_position: Option<usize>,
+    allow_trailing_empty: bool,
+    finished: bool,
+    pat: P,
+}
+impl<'a, P: AsciiPattern> SplitInternal<'a, P> {
+    fn new(haystack: &'a AsciiStr, pat: P, allow_trailing_empty: bool) -> Self {
+        SplitInternal {
+            haystack,
+            start: 0,
+            end: haystack.len(),
+            position: 0,
+            back_position: Some(haystack.len()),
+            allow_trailing_empty,
+            finished: false,
+            pat,
+        }
+    }
+
+    /// # Safety
+    /// `from..to` must be within `haystack`.
+    #[inline]
+    unsafe fn slice(&self, from: usize, to: usize) -> &'a AsciiStr {
+        // SAFETY: Caller guarantees that the range is in bounds.
+        unsafe { self.haystack.as_slice().get_unchecked(from..to) }.into()
+    }
+
+    fn find_front(&mut self) -> Option<(usize, usize)> {
+        if self.position > self.end {
+            return None;
+        }
+        // SAFETY: `position <= end <= haystack.len()`
+        let remaining = unsafe { self.slice(self.position, self.end) };
+        let offset = self.position;
+        self.pat
+            .find_in(remaining)
+            .map(|(from, to)| (offset + from, offset + to))
+    }
+
+    fn find_back(&mut self) -> Option<(usize, usize)> {
+        let back_position = self.back_position?;
+        if self.start > back_position {
+            return None;
+        }
+        // SAFETY: `start <= back_position <= haystack.len()`
+        let remaining = unsafe { self.slice(self.start, back_position) };
+        let offset = self.start;
+        self.pat
+            .rfind_in(remaining)
+            .map(|(from, to)| (offset + from, offset + to))
+    }
+
+    /// Moves the search position from the front past a match.
+    fn advance_front(&mut self, from: usize, to: usize) {
+        self.position = if from == to { to + 1 } else { to };
+    }
+
+    /// Moves the search position from the back before a match.
+    fn advance_back(&mut self, from: usize, to: usize) {
+        self.back_position = if from == to {
+            from.checked_sub(1)
+        } else {
+            Some(from)
+        };
+    }
+
+    fn get_end(&mut self) -> Option<&'a AsciiStr> {
+        if !self.finished {
+            self.finished = true;
+            if self.allow_trailing_empty || self.end > self.start {
+                // SAFETY: `start <= end <= haystack.len()`
+                return Some(unsafe { self.slice(self.start, self.end) });
+            }
+        }
+        None
+    }
+
+    fn next(&mut self) -> Option<&'a AsciiStr> {
+        if self.finished {
+            return None;
+        }
+        if let Some((from, to)) = self.find_front() {
+            // SAFETY: `start <= from`, as matches are searched for after `start`.
+            let part = unsafe { self.slice(self.start, from) };
+            self.start = to;
+            self.advance_front(from, to);
+            Some(part)
+        } else {
+            self.get_end()
+        }
+    }
+
+    fn next_inclusive(&mut self) -> Option<&'a AsciiStr> {
+        if self.finished {
+            return None;
+        }
+        if let Some((from, to)) = self.find_front() {
+            // SAFETY: `start <= to`, as matches are searched for after `start`.
+            let part = unsafe { self.slice(self.start, to) };
+            self.start = to;
+            self.advance_front(from, to);
+            Some(part)
+        } else {
+            self.get_end()
+        }
+    }
+
+    fn next_back(&mut self) -> Option<&'a AsciiStr> {
+        if self.finished {
+            return None;
+        }
+        if !self.allow_trailing_empty {
+            self.allow_trailing_empty = true;
+            if let Some(part) = self.next_back() {
+                if !part.is_empty() {
+                    return Some(part);
+                }
+            }
+            if self.finished {
+                return None;
+            }
+        }
+        if let Some((from, to)) = self.find_back() {
+            // SAFETY: `to <= end`, as matches are searched for before `end`.
+            let part = unsafe { self.slice(to, self.end) };
+            self.end = from;
+            self.advance_back(from, to);
+            Some(part)
+        } else {
+            self.finished = true;
+            // SAFETY: `start <= end <= haystack.len()`
+            Some(unsafe { self.slice(self.start, self.end) })
+        }
+    }
+
+    fn next_back_inclusive(&mut self) -> Option<&'a AsciiStr> {
+        if self.finished {
+            return None;
+        }
+        if !self.allow_trailing_empty {
+            self.allow_trailing_empty = true;
+            if let Some(part) = self.next_back_inclusive() {
+                if !part.is_empty() {
+                    return Some(part);
+                }
+            }
+            if self.finished {
+                return None;
+            }
+        }
+        if let Some((from, to)) = self.find_back() {
+            // SAFETY: `to <= end`, as matches are searched for before `end`.
+            let part = unsafe { self.slice(to, self.end) };
+            self.end = to;
+            self.advance_back(from, to);
+            Some(part)
+        } else {
+            self.finished = true;
+            // SAFETY: `start <= end <= haystack.len()`
+            Some(unsafe { self.slice(self.start, self.end) })
+        }
+    }
+}
+
+/// The state shared by `SplitN` and `RSplitN`.
+#[derive(Clone, Debug)]
+struct SplitNInternal<'a, P> {
+    iter: SplitInternal<'a, P>,
+    /// The number of splits remaining
+    count: usize,
+}
+impl<'a, P: AsciiPattern> SplitNInternal<'a, P> {
+    fn next(&mut self) -> Option<&'a AsciiStr> {
+        match self.count {
+            0 => None,
+            1 => {
+                self.count = 0;
+                self.iter.get_end()
+            }
+            _ => {
+                self.count -= 1;
+                self.iter.next()
+            }
+        }
+    }
+
+    fn next_back(&mut self) -> Option<&'a AsciiStr> {
+        match self.count {
+            0 => None,
+            1 => {
+                self.count = 0;
+                self.iter.get_end()
+            }
+            _ => {
+                self.count -= 1;
+                self.iter.next_back()
+            }
+        }
+    }
+}
+
+macro_rules! split_iterator {
+    (
+        $(#[$attr:meta])*
+        struct $name:ident($internal:ident);
+        fn next = $next:ident;
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug)]
+        pub struct $name<'a, P>($internal<'a, P>);
+        impl<'a, P: AsciiPattern> Iterator for $name<'a, P> {
+            type Item = &'a AsciiStr;
+            #[inline]
+            fn next(&mut self) -> Option<&'a AsciiStr> {
+                self.0.$next()
+            }
+        }
+    };
+    (
+        $(#[$attr:meta])*
+        struct $name:ident($internal:ident);
+        fn next = $next:ident;
+        fn next_back = $next_back:ident;
+    ) => {
+        split_iterator! {
+            $(#[$attr])*
+            struct $name($internal);
+            fn next = $next;
+        }
+        impl<'a, P: DoubleEndedAsciiPattern> DoubleEndedIterator for $name<'a, P> {
+            #[inline]
+            fn next_back(&mut self) -> Option<&'a AsciiStr> {
+                self.0.$next_back()
+            }
+        }
+    };
+}
+
+split_iterator! {
+    /// An iterator over parts of an `AsciiStr` separated by matches of a pattern.
+    ///
+    /// This type is created by [`AsciiStr::split()`].
+    struct Split(SplitInternal);
+    fn next = next;
+    fn next_back = next_back;
+}
+split_iterator! {
+    /// An iterator over parts of an `AsciiStr` separated by matches of a pattern,
+    /// starting from the end.
+    ///
+    /// This type is created by [`AsciiStr::rsplit()`].
+    struct RSplit(SplitInternal);
+    fn next = next_back;
+    fn next_back = next;
+}
+split_iterator! {
+    /// An iterator over at most `n` parts of an `AsciiStr` separated by matches of a pattern.
+    ///
+    /// This type is created by [`AsciiStr::splitn()`].
+    struct SplitN(SplitNInternal);
+    fn next = next;
+}
+split_iterator! {
+    /// An iterator over at most `n` parts of an `AsciiStr` separated by matches of a pattern,
+    /// starting from the end.
+    ///
+    /// This type is created by [`AsciiStr::rsplitn()`].
+    struct RSplitN(SplitNInternal);
+    fn next = next_back;
+}
+split_iterator! {
+    /// An iterator over parts of an `AsciiStr` terminated by matches of a pattern.
+    ///
+    /// This type is created by [`AsciiStr::split_terminator()`].
+    struct SplitTerminator(SplitInternal);
+    fn next = next;
+    fn next_back = next_back;
+}
+split_iterator! {
+    /// An iterator over parts of an `AsciiStr` terminated by matches of a pattern,
+    /// starting from the end.
+    ///
+    /// This type is created by [`AsciiStr::rsplit_terminator()`].
+    struct RSplitTerminator(SplitInternal);
+    fn next = next_back;
+    fn next_back = next;
+}
+split_iterator! {
+    /// An iterator over parts of an `AsciiStr` which end with a match of a pattern.
+    ///
+    /// This type is created by [`AsciiStr::split_inclusive()`].
+    struct SplitInclusive(SplitInternal);
+    fn next = next_inclusive;
+    fn next_back = next_back_inclusive;
 }
 
-/// An immutable iterator over the characters of an `AsciiStr`.
+/// An iterator over the non-empty parts of an `AsciiStr` separated by whitespace.
+///
+/// This type is created by [`AsciiStr::split_whitespace()`],
+/// [`AsciiStr::split_ascii_whitespace()`] and [`AsciiStr::split_ascii_blank()`].
 #[derive(Clone, Debug)]
-pub struct CharsRef<'a>(Iter<'a, AsciiChar>);
-impl<'a> CharsRef<'a> {
-    /// Returns the ascii string slice with the remaining characters.
-    #[must_use]
-    pub fn as_str(&self) -> &'a AsciiStr {
-        self.0.as_slice().into()
-    }
+pub struct SplitWhitespace<'a> {
+    inner: Split<'a, fn(AsciiChar) -> bool>,
 }
-impl<'a> Iterator for CharsRef<'a> {
-    type Item = &'a AsciiChar;
-    #[inline]
-    fn next(&mut self) -> Option<&'a AsciiChar> {
-        self.0.next()
-    }
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.0.size_hint()
+impl<'a> Iterator for SplitWhitespace<'a> {
+    type Item = &'a AsciiStr;
+
+    fn next(&mut self) -> Option<&'a AsciiStr> {
+        self.inner.by_ref().find(|part| !part.is_empty())
     }
 }
-impl<'a> DoubleEndedIterator for CharsRef<'a> {
-    #[inline]
-    fn next_back(&mut self) -> Option<&'a AsciiChar> {
-        self.0.next_back()
+impl<'a> DoubleEndedIterator for SplitWhitespace<'a> {
+    fn next_back(&mut self) -> Option<&'a AsciiStr> {
+        self.inner.by_ref().rev().find(|part| !part.is_empty())
     }
 }
 
-/// An iterator over parts of an `AsciiStr` separated by an `AsciiChar`.
+/// The state shared by the `[r]match[_indice]s` iterators.
 ///
-/// This type is created by [`AsciiChar::split()`](struct.AsciiChar.html#method.split).
+/// Matches are searched for in `haystack[start..end]`, which shrinks from the front when
+/// iterating forwards and from the back when iterating backwards.
 #[derive(Clone, Debug)]
-struct Split<'a> {
-    on: AsciiChar,
-    ended: bool,
-    chars: Chars<'a>,
-}
-impl<'a> Iterator for Split<'a> {
-    type Item = &'a AsciiStr;
-
-    fn next(&mut self) -> Option<&'a AsciiStr> {
-        if !self.ended {
-            let start: &AsciiStr = self.chars.as_str();
-            let split_on = self.on;
+struct MatchIndicesInternal<'a, P> {
+    haystack: &'a AsciiStr,
+    start: usize,
+    end: usize,
+    finished: bool,
+    pat: P,
+}
+impl<'a, P: AsciiPattern> MatchIndicesInternal<'a, P> {
+    fn new(haystack: &'a AsciiStr, pat: P) -> Self {
+        MatchIndicesInternal {
+            haystack,
+            start: 0,
+            end: haystack.len(),
+            finished: false,
+            pat,
+        }
+    }
 
-            if let Some(at) = self.chars.position(|ch| ch == split_on) {
-                // SAFETY: `at` is guaranteed to be in bounds, as `position` returns `Ok(0..len)`.
-                Some(unsafe { start.as_slice().get_unchecked(..at).into() })
+    fn next(&mut self) -> Option<(usize, &'a AsciiStr)> {
+        if self.finished {
+            return None;
+        }
+        // SAFETY: `start <= end <= haystack.len()` is upheld by `next()` and `next_back()`.
+        let remaining: &AsciiStr =
+            unsafe { self.haystack.as_slice().get_unchecked(self.start..self.end) }.into();
+        if let Some((from, to)) = self.pat.find_in(remaining) {
+            let (from, to) = (self.start + from, self.start + to);
+            if from != to {
+                self.start = to;
+            } else if from < self.end {
+                // Step over a character so that an empty match isn't found again.
+                self.start = from + 1;
             } else {
-                self.ended = true;
-                Some(start)
+                self.finished = true;
             }
+            // SAFETY: The pattern returned a range within `remaining`.
+            let matched = unsafe { self.haystack.as_slice().get_unchecked(from..to) };
+            Some((from, matched.into()))
         } else {
+            self.finished = true;
             None
         }
     }
-}
-impl<'a> DoubleEndedIterator for Split<'a> {
-    fn next_back(&mut self) -> Option<&'a AsciiStr> {
-        if !self.ended {
-            let start: &AsciiStr = self.chars.as_str();
-            let split_on = self.on;
 
-            if let Some(at) = self.chars.rposition(|ch| ch == split_on) {
-                // SAFETY: `at` is guaranteed to be in bounds, as `rposition` returns `Ok(0..len)`, and slices `1..`, `2..`, etc... until `len..` inclusive, are valid.
-                Some(unsafe { start.as_slice().get_unchecked(at + 1..).into() })
+    fn next_back(&mut self) -> Option<(usize, &'a AsciiStr)> {
+        if self.finished {
+            return None;
+        }
+        // SAFETY: `start <= end <= haystack.len()` is upheld by `next()` and `next_back()`.
+        let remaining: &AsciiStr =
+            unsafe { self.haystack.as_slice().get_unchecked(self.start..self.end) }.into();
+        if let Some((from, to)) = self.pat.rfind_in(remaining) {
+            let (from, to) = (self.start + from, self.start + to);
+            if from != to {
+                self.end = from;
+            } else if to > self.start {
+                // Step over a character so that an empty match isn't found again.
+                self.end = to - 1;
             } else {
-                self.ended = true;
-                Some(start)
+                self.finished = true;
             }
+            // SAFETY: The pattern returned a range within `remaining`.
+            let matched = unsafe { self.haystack.as_slice().get_unchecked(from..to) };
+            Some((from, matched.into()))
         } else {
+            self.finished = true;
             None
         }
     }
 }
 
-/// An iterator over the lines of the internal character array.
+/// An iterator over the non-overlapping matches of a pattern in an `AsciiStr`.
+///
+/// This type is created by [`AsciiStr::matches()`].
 #[derive(Clone, Debug)]
-struct Lines<'a> {
-    string: &'a AsciiStr,
-}
-impl<'a> Iterator for Lines<'a> {
+pub struct Matches<'a, P>(MatchIndicesInternal<'a, P>);
+impl<'a, P: AsciiPattern> Iterator for Matches<'a, P> {
     type Item = &'a AsciiStr;
-
+    #[inline]
     fn next(&mut self) -> Option<&'a AsciiStr> {
-        if let Some(idx) = self
-            .string
-            .chars()
-            .position(|chr| chr == AsciiChar::LineFeed)
-        {
-            // SAFETY: `idx` is guaranteed to be `1..len`, as we get it from `position` as `0..len` and make sure it's not `0`.
-            let line = if idx > 0
-                && *unsafe { self.string.as_slice().get_unchecked(idx - 1) }
-                    == AsciiChar::CarriageReturn
-            {
-                // SAFETY: As per above, `idx` is guaranteed to be `1..len`
-                unsafe { self.string.as_slice().get_unchecked(..idx - 1).into() }
-            } else {
-                // SAFETY: As per above, `idx` is guaranteed to be `0..len`
-                unsafe { self.string.as_slice().get_unchecked(..idx).into() }
-            };
-            // SAFETY: As per above, `idx` is guaranteed to be `0..len`, so at the extreme, slicing `len..` is a valid empty slice.
-            self.string = unsafe { self.string.as_slice().get_unchecked(idx + 1..).into() };
-            Some(line)
-        } else if self.string.is_empty() {
-            None
-        } else {
-            let line = self.string;
-            // SAFETY: An empty string is a valid string.
-            self.string = unsafe { AsciiStr::from_ascii_unchecked(b"") };
-            Some(line)
-        }
+        self.0.next().map(|(_, matched)| matched)
     }
 }
-
-impl<'a> DoubleEndedIterator for Lines<'a> {
+impl<'a, P: DoubleEndedAsciiPattern> DoubleEndedIterator for Matches<'a, P> {
+    #[inline]
     fn next_back(&mut self) -> Option<&'a AsciiStr> {
-        if self.string.is_empty() {
-            return None;
-        }
-
-        // If we end with `LF` / `CR/LF`, remove them
-        if let Some(AsciiChar::LineFeed) = self.string.last() {
-            // SAFETY: `last()` returned `Some`, so our len is at least 1.
-            self.string = unsafe {
-                self.string
-                    .as_slice()
-                    .get_unchecked(..self.string.len() - 1)
-                    .into()
-            };
+        self.0.next_back().map(|(_, matched)| matched)
+    }
+}
 
-            if let Some(AsciiChar::CarriageReturn) = self.string.last() {
-                // SAFETY: `last()` returned `Some`, so our len is at least 1.
-                self.string = unsafe {
-                    self.string
-                        .as_slice()
-                        .get_unchecked(..self.string.len() - 1)
-                        .into()
-                };
-            }
-        }
+/// An iterator over the non-overlapping matches of a pattern in an `AsciiStr`,
+/// starting from the end.
+///
+/// This type is created by [`AsciiStr::rmatches()`].
+#[derive(Clone, Debug)]
+pub struct RMatches<'a, P>(MatchIndicesInternal<'a, P>);
+impl<'a, P: AsciiPattern> Iterator for RMatches<'a, P> {
+    type Item = &'a AsciiStr;
+    #[inline]
+    fn next(&mut self) -> Option<&'a AsciiStr> {
+        self.0.next_back().map(|(_, matched)| matched)
+    }
+}
+impl<'a, P: DoubleEndedAsciiPattern> DoubleEndedIterator for RMatches<'a, P> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a AsciiStr> {
+        self.0.next().map(|(_, matched)| matched)
+    }
+}
 
-        // Get the position of the first `LF` from the end.
-        let lf_rev_pos = self
-            .string
-            .chars()
-            .rev()
-            .position(|ch| ch == AsciiChar::LineFeed)
-            .unwrap_or_else(|| self.string.len());
+/// An iterator over the non-overlapping matches of a pattern in an `AsciiStr`
+/// and the byte indices they start at.
+///
+/// This type is created by [`AsciiStr::match_indices()`].
+#[derive(Clone, Debug)]
+pub struct MatchIndices<'a, P>(MatchIndicesInternal<'a, P>);
+impl<'a, P: AsciiPattern> Iterator for MatchIndices<'a, P> {
+    type Item = (usize, &'a AsciiStr);
+    #[inline]
+    fn next(&mut self) -> Option<(usize, &'a AsciiStr)> {
+        self.0.next()
+    }
+}
+impl<'a, P: DoubleEndedAsciiPattern> DoubleEndedIterator for MatchIndices<'a, P> {
+    #[inline]
+    fn next_back(&mut self) -> Option<(usize, &'a AsciiStr)> {
+        self.0.next_back()
+    }
+}
 
-        // SAFETY: `lf_rev_pos` will be in range `0..=len`, so `len - lf_rev_pos`
-        //         will be within `0..=len`, making it correct as a start and end
-        //         point for the strings.
-        let line = unsafe {
-            self.string
-                .as_slice()
-                .get_unchecked(self.string.len() - lf_rev_pos..)
-                .into()
-        };
-        self.string = unsafe {
-            self.string
-                .as_slice()
-                .get_unchecked(..self.string.len() - lf_rev_pos)
-                .into()
-        };
-        Some(line)
+/// An iterator over the non-overlapping matches of a pattern in an `AsciiStr`
+/// and the byte indices they start at, starting from the end.
+///
+/// This type is created by [`AsciiStr::rmatch_indices()`].
+#[derive(Clone, Debug)]
+pub struct RMatchIndices<'a, P>(MatchIndicesInternal<'a, P>);
+impl<'a, P: AsciiPattern> Iterator for RMatchIndices<'a, P> {
+    type Item = (usize, &'a AsciiStr);
+    #[inline]
+    fn next(&mut self) -> Option<(usize, &'a AsciiStr)> {
+        self.0.next_back()
+    }
+}
+impl<'a, P: DoubleEndedAsciiPattern> DoubleEndedIterator for RMatchIndices<'a, P> {
+    #[inline]
+    fn next_back(&mut self) -> Option<(usize, &'a AsciiStr)> {
+        self.0.next()
     }
 }
 
 /// Error that is returned when a sequence of `u8` are not all ASCII.
 ///
 /// Is used by `As[Mut]AsciiStr` and the `from_ascii` method on `AsciiStr` and `AsciiString`.
+///
+/// Besides the position of the first non-ASCII byte, it tells which byte that was, and which
+/// character it starts if the input is valid UTF-8 at that position.
+///
+/// # Examples
+/// ```
+/// # use ascii::AsciiStr;
+/// let error = AsciiStr::from_ascii("Gr\u{fc}\u{df}e").unwrap_err();
+/// assert_eq!(error.valid_up_to(), 2);
+/// assert_eq!(error.non_ascii_byte(), Some(0xc3));
+/// assert_eq!(error.non_ascii_char(), Some('\u{fc}'));
+/// assert_eq!(error.error_len(), Some(2));
+/// assert_eq!(error.to_string(), "non-ASCII byte 0xC3 at offset 2");
+///
+/// let error = AsciiStr::from_ascii(b"\xff\xfe").unwrap_err();
+/// assert_eq!(error.non_ascii_byte(), Some(0xff));
+/// assert_eq!(error.non_ascii_char(), None);
+/// assert_eq!(error.error_len(), Some(1));
+///
+/// let error = AsciiStr::from_ascii(b"Gr\xc3").unwrap_err();
+/// assert_eq!(error.error_len(), Some(1));
+/// ```
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub struct AsAsciiStrError(usize);
+pub struct AsAsciiStrError {
+    valid_up_to: usize,
+    /// `None` if the error is caused by a range being out of bounds.
+    byte: Option<u8>,
+    /// The length of the UTF-8 encoded character starting with `byte`,
+    /// or 0 if the bytes there are not valid UTF-8.
+    utf8_len: u8,
+    code_point: u32,
+}
 
 const ERRORMSG_STR: &str = "one or more bytes are not ASCII";
 
 impl AsAsciiStrError {
+    /// Creates an error for the non-ASCII byte at `bytes[valid_up_to]`.
+    pub(crate) const fn new(bytes: &[u8], valid_up_to: usize) -> Self {
+        #![allow(clippy::indexing_slicing)] // .get() is not const yet
+        let (utf8_len, code_point) = decode_utf8(bytes, valid_up_to);
+        AsAsciiStrError {
+            valid_up_to,
+            byte: Some(bytes[valid_up_to]),
+            utf8_len,
+            code_point,
+        }
+    }
+
+    /// Creates an error for a range that is out of bounds for a slice of length `len`.
+    pub(crate) const fn out_of_bounds(len: usize) -> Self {
+        AsAsciiStrError {
+            valid_up_to: len,
+            byte: None,
+            utf8_len: 0,
+            code_point: 0,
+        }
+    }
+
+    /// Moves the position of the error by `offset`, for errors in a subslice.
+    pub(crate) const fn with_offset(mut self, offset: usize) -> Self {
+        self.valid_up_to += offset;
+        self
+    }
+
     /// Returns the index of the first non-ASCII byte.
     ///
     /// It is the maximum index such that `from_ascii(input[..index])` would return `Ok(_)`.
     #[inline]
     #[must_use]
     pub const fn valid_up_to(self) -> usize {
-        self.0
+        self.valid_up_to
+    }
+
+    /// Returns the first non-ASCII byte,
+    /// or `None` if the error was caused by a range being out of bounds.
+    #[inline]
+    #[must_use]
+    pub const fn non_ascii_byte(self) -> Option<u8> {
+        self.byte
+    }
+
+    /// Returns the character that starts with the first non-ASCII byte,
+    /// or `None` if the bytes there are not valid UTF-8.
+    ///
+    /// This is always `Some` for errors from converting a `str`,
+    /// unless a range was out of bounds.
+    #[inline]
+    #[must_use]
+    pub fn non_ascii_char(self) -> Option<char> {
+        if self.utf8_len == 0 {
+            None
+        } else {
+            char::from_u32(self.code_point)
+        }
+    }
+
+    /// Returns the number of bytes to skip to get past the first non-ASCII byte.
+    ///
+    /// This is the length of the UTF-8 encoded character if the bytes starting at
+    /// [`valid_up_to()`](#method.valid_up_to) are valid UTF-8, and 1 otherwise,
+    /// including when a UTF-8 sequence is cut off at the end of the input.
+    /// Returns `None` only if the error was caused by a range being out of bounds.
+    #[inline]
+    #[must_use]
+    pub fn error_len(self) -> Option<usize> {
+        match (self.byte, self.utf8_len) {
+            (None, _) => None,
+            (Some(_), 0) => Some(1),
+            (Some(_), len) => Some(usize::from(len)),
+        }
     }
+
     #[cfg(not(feature = "std"))]
     /// Returns a description for this error, like `std::error::Error::description`.
     #[inline]
@@ -890,8 +2248,58 @@ impl AsAsciiStrError {
 }
 impl fmt::Display for AsAsciiStrError {
     fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
-        write!(fmtr, "the byte at index {} is not ASCII", self.0)
+        match self.byte {
+            Some(byte) => write!(
+                fmtr,
+                "non-ASCII byte 0x{:02X} at offset {}",
+                byte, self.valid_up_to
+            ),
+            None => write!(
+                fmtr,
+                "range is out of bounds of a slice with length {}",
+                self.valid_up_to
+            ),
+        }
+    }
+}
+
+/// Decodes the UTF-8 encoded character starting at `bytes[start]`,
+/// and returns its length and code point, or `(0, 0)` if the bytes there are not valid UTF-8.
+///
+/// Only multi-byte sequences are decoded, as this is used for non-ASCII bytes.
+#[allow(clippy::cast_lossless, clippy::indexing_slicing)] // `u32::from()` and .get() are not const
+const fn decode_utf8(bytes: &[u8], start: usize) -> (u8, u32) {
+    let first = bytes[start];
+    // The valid ranges for the second byte exclude overlong encodings, surrogates and
+    // code points above U+10FFFF.
+    let (len, second_min, second_max, lead_bits) = match first {
+        0xc2..=0xdf => (2, 0x80, 0xbf, first & 0x1f),
+        0xe0 => (3, 0xa0, 0xbf, first & 0x0f),
+        0xe1..=0xec | 0xee..=0xef => (3, 0x80, 0xbf, first & 0x0f),
+        0xed => (3, 0x80, 0x9f, first & 0x0f),
+        0xf0 => (4, 0x90, 0xbf, first & 0x07),
+        0xf1..=0xf3 => (4, 0x80, 0xbf, first & 0x07),
+        0xf4 => (4, 0x80, 0x8f, first & 0x07),
+        _ => return (0, 0),
+    };
+    if bytes.len() - start < len as usize {
+        return (0, 0);
+    }
+    let second = bytes[start + 1];
+    if second < second_min || second > second_max {
+        return (0, 0);
+    }
+    let mut code_point = (lead_bits as u32) << 6 | (second & 0x3f) as u32;
+    let mut i = 2;
+    while i < len as usize {
+        let continuation = bytes[start + i];
+        if continuation & 0xc0 != 0x80 {
+            return (0, 0);
+        }
+        code_point = code_point << 6 | (continuation & 0x3f) as u32;
+        i += 1;
     }
+    (len, code_point)
 }
 #[cfg(feature = "std")]
 impl Error for AsAsciiStrError {
@@ -1099,7 +2507,7 @@ impl AsAsciiStr for [AsciiChar] {
     {
         match self.get(range) {
             Some(slice) => Ok(slice.into()),
-            None => Err(AsAsciiStrError(self.len())),
+            None => Err(AsAsciiStrError::out_of_bounds(self.len())),
         }
     }
 
@@ -1126,7 +2534,7 @@ impl AsMutAsciiStr for [AsciiChar] {
         let len = self.len();
         match self.get_mut(range) {
             Some(slice) => Ok(slice.into()),
-            None => Err(AsAsciiStrError(len)),
+            None => Err(AsAsciiStrError::out_of_bounds(len)),
         }
     }
     #[inline]
@@ -1143,24 +2551,22 @@ impl AsAsciiStr for [u8] {
         R: SliceIndex<[u8], Output = [u8]>,
     {
         if let Some(slice) = self.get(range) {
-            slice.as_ascii_str().map_err(|AsAsciiStrError(not_ascii)| {
+            slice.as_ascii_str().map_err(|error| {
                 let offset = slice.as_ptr() as usize - self.as_ptr() as usize;
-                AsAsciiStrError(offset + not_ascii)
+                error.with_offset(offset)
             })
         } else {
-            Err(AsAsciiStrError(self.len()))
+            Err(AsAsciiStrError::out_of_bounds(self.len()))
         }
     }
 
     fn as_ascii_str(&self) -> Result<&AsciiStr, AsAsciiStrError> {
-        // is_ascii is likely optimized
-        if self.is_ascii() {
-            // SAFETY: `is_ascii` guarantees all bytes are within ascii range.
+        let valid_up_to = ascii_prefix_len(self);
+        if valid_up_to == self.len() {
+            // SAFETY: `ascii_prefix_len` guarantees all bytes are within ascii range.
             unsafe { Ok(self.as_ascii_str_unchecked()) }
         } else {
-            Err(AsAsciiStrError(
-                self.iter().take_while(|&b| b.is_ascii()).count(),
-            ))
+            Err(AsAsciiStrError::new(self, valid_up_to))
         }
     }
 
@@ -1178,26 +2584,22 @@ impl AsMutAsciiStr for [u8] {
         let (ptr, len) = (self.as_ptr(), self.len());
         if let Some(slice) = self.get_mut(range) {
             let slice_ptr = slice.as_ptr();
-            slice
-                .as_mut_ascii_str()
-                .map_err(|AsAsciiStrError(not_ascii)| {
-                    let offset = slice_ptr as usize - ptr as usize;
-                    AsAsciiStrError(offset + not_ascii)
-                })
+            slice.as_mut_ascii_str().map_err(|error| {
+                let offset = slice_ptr as usize - ptr as usize;
+                error.with_offset(offset)
+            })
         } else {
-            Err(AsAsciiStrError(len))
+            Err(AsAsciiStrError::out_of_bounds(len))
         }
     }
 
     fn as_mut_ascii_str(&mut self) -> Result<&mut AsciiStr, AsAsciiStrError> {
-        // is_ascii() is likely optimized
-        if self.is_ascii() {
-            // SAFETY: `is_ascii` guarantees all bytes are within ascii range.
+        let valid_up_to = ascii_prefix_len(self);
+        if valid_up_to == self.len() {
+            // SAFETY: `ascii_prefix_len` guarantees all bytes are within ascii range.
             unsafe { Ok(self.as_mut_ascii_str_unchecked()) }
         } else {
-            Err(AsAsciiStrError(
-                self.iter().take_while(|&b| b.is_ascii()).count(),
-            ))
+            Err(AsAsciiStrError::new(self, valid_up_to))
         }
     }
 
@@ -1235,7 +2637,7 @@ impl AsMutAsciiStr for str {
         let bytes = unsafe { self.as_bytes_mut() };
         match bytes.get_mut(range) {
             // Valid ascii slice
-            Some(slice) if slice.is_ascii() => {
+            Some(slice) if ascii_prefix_len(slice) == slice.len() => {
                 // SAFETY: All bytes are ascii, so this cast is valid
                 let ptr = slice.as_mut_ptr().cast::<AsciiChar>();
                 let len = slice.len();
@@ -1248,19 +2650,21 @@ impl AsMutAsciiStr for str {
                 }
             }
             Some(slice) => {
-                let not_ascii_len = slice.iter().copied().take_while(u8::is_ascii).count();
+                let error = AsAsciiStrError::new(slice, ascii_prefix_len(slice));
                 let offset = slice.as_ptr() as usize - self.as_ptr() as usize;
 
-                Err(AsAsciiStrError(offset + not_ascii_len))
+                Err(error.with_offset(offset))
             }
-            None => Err(AsAsciiStrError(self.len())),
+            None => Err(AsAsciiStrError::out_of_bounds(self.len())),
         }
     }
     fn as_mut_ascii_str(&mut self) -> Result<&mut AsciiStr, AsAsciiStrError> {
-        match self.bytes().position(|b| !b.is_ascii()) {
-            Some(index) => Err(AsAsciiStrError(index)),
-            // SAFETY: All bytes were iterated, and all were ascii
-            None => unsafe { Ok(self.as_mut_ascii_str_unchecked()) },
+        let valid_up_to = ascii_prefix_len(self.as_bytes());
+        if valid_up_to == self.len() {
+            // SAFETY: `ascii_prefix_len` guarantees all bytes are within ascii range.
+            unsafe { Ok(self.as_mut_ascii_str_unchecked()) }
+        } else {
+            Err(AsAsciiStrError::new(self.as_bytes(), valid_up_to))
         }
     }
     #[inline]
@@ -1369,18 +2773,29 @@ mod tests {
 
     #[test]
     fn as_ascii_str() {
-        macro_rules! err {{$i:expr} => {Err(AsAsciiStrError($i))}}
         let s = "abčd";
         let b = s.as_bytes();
-        assert_eq!(s.as_ascii_str(), err!(2));
-        assert_eq!(b.as_ascii_str(), err!(2));
+        assert_eq!(
+            s.as_ascii_str().map_err(AsAsciiStrError::valid_up_to),
+            Err(2)
+        );
+        assert_eq!(
+            b.as_ascii_str().map_err(AsAsciiStrError::valid_up_to),
+            Err(2)
+        );
         let a: &AsciiStr = [AsciiChar::a, AsciiChar::b][..].as_ref();
         assert_eq!(s[..2].as_ascii_str(), Ok(a));
         assert_eq!(b[..2].as_ascii_str(), Ok(a));
         assert_eq!(s.slice_ascii(..2), Ok(a));
         assert_eq!(b.slice_ascii(..2), Ok(a));
-        assert_eq!(s.slice_ascii(..=2), err!(2));
-        assert_eq!(b.slice_ascii(..=2), err!(2));
+        assert_eq!(
+            s.slice_ascii(..=2).map_err(AsAsciiStrError::valid_up_to),
+            Err(2)
+        );
+        assert_eq!(
+            b.slice_ascii(..=2).map_err(AsAsciiStrError::valid_up_to),
+            Err(2)
+        );
         assert_eq!(s.get_ascii(4), Some(AsciiChar::d));
         assert_eq!(b.get_ascii(4), Some(AsciiChar::d));
         assert_eq!(s.get_ascii(3), None);
@@ -1391,14 +2806,71 @@ mod tests {
         assert_eq!(a.get_ascii(a.len()), None);
     }
 
+    #[test]
+    fn as_ascii_str_error_details() {
+        fn details(error: AsAsciiStrError) -> (usize, Option<u8>, Option<usize>, Option<char>) {
+            (
+                error.valid_up_to(),
+                error.non_ascii_byte(),
+                error.error_len(),
+                error.non_ascii_char(),
+            )
+        }
+        let error = "a\u{e9}".as_ascii_str().unwrap_err();
+        assert_eq!(details(error), (1, Some(0xc3), Some(2), Some('\u{e9}')));
+        let error = "ab\u{20ac}".as_ascii_str().unwrap_err();
+        assert_eq!(details(error), (2, Some(0xe2), Some(3), Some('\u{20ac}')));
+        let error = "\u{1f600}".as_ascii_str().unwrap_err();
+        assert_eq!(details(error), (0, Some(0xf0), Some(4), Some('\u{1f600}')));
+        let error = "\u{10ffff}".as_ascii_str().unwrap_err();
+        assert_eq!(error.non_ascii_char(), Some('\u{10ffff}'));
+        // Overlong, surrogate, above U+10FFFF, truncated and lone continuation bytes.
+        for bytes in &[
+            &b"\xc0\x80"[..],
+            b"\xe0\x80\x80",
+            b"\xed\xa0\x80",
+            b"\xf4\x90\x80\x80",
+            b"\xe2\x82",
+            b"\x80",
+            b"\xff",
+        ] {
+            let error = bytes.as_ascii_str().unwrap_err();
+            assert_eq!(details(error), (0, Some(bytes[0]), Some(1), None));
+        }
+        let error = AsciiStr::from_ascii_bytes(b"xy\xce\xbb").unwrap_err();
+        assert_eq!(details(error), (2, Some(0xce), Some(2), Some('\u{3bb}')));
+
+        let error = "0123\u{e9}".slice_ascii(2..).unwrap_err();
+        assert_eq!(details(error), (4, Some(0xc3), Some(2), Some('\u{e9}')));
+        let error = "0123".slice_ascii(2..10).unwrap_err();
+        assert_eq!(details(error), (4, None, None, None));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn as_ascii_str_error_display() {
+        let error = "0123456789abcdefg\u{e9}".as_ascii_str().unwrap_err();
+        assert_eq!(error.to_string(), "non-ASCII byte 0xC3 at offset 17");
+        let error = "abc".slice_ascii(..4).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "range is out of bounds of a slice with length 3"
+        );
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn cstr_as_ascii_str() {
         use std::ffi::CStr;
-        macro_rules! err {{$i:expr} => {Err(AsAsciiStrError($i))}}
         let cstr = CStr::from_bytes_with_nul(b"a\xbbcde\xffg\0").unwrap();
-        assert_eq!(cstr.as_ascii_str(), err!(1));
-        assert_eq!(cstr.slice_ascii(2..), err!(5));
+        assert_eq!(
+            cstr.as_ascii_str().map_err(AsAsciiStrError::valid_up_to),
+            Err(1)
+        );
+        assert_eq!(
+            cstr.slice_ascii(2..).map_err(AsAsciiStrError::valid_up_to),
+            Err(5)
+        );
         assert_eq!(cstr.get_ascii(5), None);
         assert_eq!(cstr.get_ascii(6), Some(AsciiChar::g));
         assert_eq!(cstr.get_ascii(7), None);
@@ -1412,23 +2884,42 @@ mod tests {
     #[test]
     #[cfg(feature = "alloc")]
     fn as_mut_ascii_str() {
-        macro_rules! err {{$i:expr} => {Err(AsAsciiStrError($i))}}
         let mut s: String = "abčd".to_string();
         let mut b: Vec<u8> = s.clone().into();
         let mut first = [AsciiChar::a, AsciiChar::b];
         let mut second = [AsciiChar::d];
-        assert_eq!(s.as_mut_ascii_str(), err!(2));
-        assert_eq!(b.as_mut_ascii_str(), err!(2));
-        assert_eq!(s.slice_ascii_mut(..), err!(2));
-        assert_eq!(b.slice_ascii_mut(..), err!(2));
+        assert_eq!(
+            s.as_mut_ascii_str().map_err(AsAsciiStrError::valid_up_to),
+            Err(2)
+        );
+        assert_eq!(
+            b.as_mut_ascii_str().map_err(AsAsciiStrError::valid_up_to),
+            Err(2)
+        );
+        assert_eq!(
+            s.slice_ascii_mut(..).map_err(AsAsciiStrError::valid_up_to),
+            Err(2)
+        );
+        assert_eq!(
+            b.slice_ascii_mut(..).map_err(AsAsciiStrError::valid_up_to),
+            Err(2)
+        );
         assert_eq!(s[..2].as_mut_ascii_str(), Ok((&mut first[..]).into()));
         assert_eq!(b[..2].as_mut_ascii_str(), Ok((&mut first[..]).into()));
         assert_eq!(s.slice_ascii_mut(0..2), Ok((&mut first[..]).into()));
         assert_eq!(b.slice_ascii_mut(0..2), Ok((&mut first[..]).into()));
         assert_eq!(s.slice_ascii_mut(4..), Ok((&mut second[..]).into()));
         assert_eq!(b.slice_ascii_mut(4..), Ok((&mut second[..]).into()));
-        assert_eq!(s.slice_ascii_mut(4..=10), err!(5));
-        assert_eq!(b.slice_ascii_mut(4..=10), err!(5));
+        assert_eq!(
+            s.slice_ascii_mut(4..=10)
+                .map_err(AsAsciiStrError::valid_up_to),
+            Err(5)
+        );
+        assert_eq!(
+            b.slice_ascii_mut(4..=10)
+                .map_err(AsAsciiStrError::valid_up_to),
+            Err(5)
+        );
     }
 
     #[test]
@@ -1652,6 +3143,301 @@ mod tests {
         assert_eq!("".split('s').next(), Some("")); // str.split() also produces one element
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn split_family() {
+        fn collect<'a>(iter: impl Iterator<Item = &'a AsciiStr>) -> Vec<&'a str> {
+            iter.map(AsciiStr::as_str).collect()
+        }
+        for &haystack in &["", ",", "a,b,,c,", ",,a", "a,,,b", "abc"] {
+            let s = haystack.as_ascii_str().unwrap();
+            let comma = AsciiChar::Comma;
+            let sub = ",,".as_ascii_str().unwrap();
+            let empty = "".as_ascii_str().unwrap();
+            assert_eq!(
+                collect(s.split(comma)),
+                haystack.split(',').collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.split(comma).rev()),
+                haystack.split(',').rev().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.rsplit(comma)),
+                haystack.rsplit(',').collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.split(sub)),
+                haystack.split(",,").collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.rsplit(sub)),
+                haystack.rsplit(",,").collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.split(empty)),
+                haystack.split("").collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.rsplit(empty)),
+                haystack.rsplit("").collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.split_terminator(comma)),
+                haystack.split_terminator(',').collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.split_terminator(comma).rev()),
+                haystack.split_terminator(',').rev().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.rsplit_terminator(comma)),
+                haystack.rsplit_terminator(',').collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.split_terminator(sub)),
+                haystack.split_terminator(",,").collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.split_inclusive(comma)),
+                haystack.split_inclusive(',').collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.split_inclusive(comma).rev()),
+                haystack.split_inclusive(',').rev().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.split_inclusive(sub)),
+                haystack.split_inclusive(",,").collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.split_inclusive(empty)),
+                haystack.split_inclusive("").collect::<Vec<_>>()
+            );
+            assert_eq!(
+                s.split_once(comma).map(|(a, b)| (a.as_str(), b.as_str())),
+                haystack.split_once(',')
+            );
+            assert_eq!(
+                s.rsplit_once(comma).map(|(a, b)| (a.as_str(), b.as_str())),
+                haystack.rsplit_once(',')
+            );
+            assert_eq!(
+                s.split_once(sub).map(|(a, b)| (a.as_str(), b.as_str())),
+                haystack.split_once(",,")
+            );
+            assert_eq!(
+                s.rsplit_once(sub).map(|(a, b)| (a.as_str(), b.as_str())),
+                haystack.rsplit_once(",,")
+            );
+            for n in 0..5 {
+                assert_eq!(
+                    collect(s.splitn(n, comma)),
+                    haystack.splitn(n, ',').collect::<Vec<_>>()
+                );
+                assert_eq!(
+                    collect(s.rsplitn(n, comma)),
+                    haystack.rsplitn(n, ',').collect::<Vec<_>>()
+                );
+                assert_eq!(
+                    collect(s.splitn(n, sub)),
+                    haystack.splitn(n, ",,").collect::<Vec<_>>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn split_by_predicate() {
+        let s = "a1b22c".as_ascii_str().unwrap();
+        let mut iter = s.split(|ch: AsciiChar| ch.is_ascii_digit());
+        assert_eq!(iter.next_back(), Some("c".as_ascii_str().unwrap()));
+        assert_eq!(iter.next(), Some("a".as_ascii_str().unwrap()));
+        assert_eq!(iter.next_back(), Some("".as_ascii_str().unwrap()));
+        assert_eq!(iter.next(), Some("b".as_ascii_str().unwrap()));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn split_whitespace() {
+        fn collect<'a>(iter: impl Iterator<Item = &'a AsciiStr>) -> Vec<&'a str> {
+            iter.map(AsciiStr::as_str).collect()
+        }
+        for &haystack in &["", "  ", "a", " a  b\tc\n", "a\x0bb\x0cc\r\nd "] {
+            let s = haystack.as_ascii_str().unwrap();
+            assert_eq!(
+                collect(s.split_whitespace()),
+                haystack.split_whitespace().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.split_whitespace().rev()),
+                haystack.split_whitespace().rev().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.split_ascii_whitespace()),
+                haystack.split_ascii_whitespace().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                collect(s.split_ascii_whitespace().rev()),
+                haystack.split_ascii_whitespace().rev().collect::<Vec<_>>()
+            );
+        }
+        let s = " a\tb \r\n c\t".as_ascii_str().unwrap();
+        assert_eq!(collect(s.split_ascii_blank()), ["a", "b", "\r\n", "c"]);
+        let mut iter = s.split_ascii_blank();
+        assert_eq!(iter.next_back(), Some("c".as_ascii_str().unwrap()));
+        assert_eq!(iter.next(), Some("a".as_ascii_str().unwrap()));
+        assert_eq!(iter.next_back(), Some("\r\n".as_ascii_str().unwrap()));
+        assert_eq!(iter.next_back(), Some("b".as_ascii_str().unwrap()));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn prefix_suffix() {
+        let s = "--key=value--".as_ascii_str().unwrap();
+        let dashes = "--".as_ascii_str().unwrap();
+        let empty = "".as_ascii_str().unwrap();
+        assert!(s.starts_with(dashes));
+        assert!(s.ends_with(dashes));
+        assert!(s.starts_with(empty));
+        assert!(s.ends_with(empty));
+        assert!(s.starts_with(AsciiChar::Minus));
+        assert!(!s.starts_with(AsciiChar::k));
+        assert!(s.ends_with([AsciiChar::Minus, AsciiChar::Plus]));
+        assert!(!empty.starts_with(AsciiChar::Minus));
+        assert!(!dashes.starts_with(s));
+        assert_eq!(s.strip_prefix(dashes), Some(&s[2..]));
+        assert_eq!(s.strip_suffix(dashes), Some(&s[..11]));
+        assert_eq!(s.strip_prefix(empty), Some(s));
+        assert_eq!(s.strip_suffix(empty), Some(s));
+        assert_eq!(s.strip_prefix(AsciiChar::k), None);
+        assert_eq!(s.strip_suffix(AsciiChar::is_alphabetic), None);
+        assert_eq!(s.trim_matches(AsciiChar::Minus), "key=value");
+        assert_eq!(s.trim_start_matches(AsciiChar::Minus), "key=value--");
+        assert_eq!(s.trim_end_matches(AsciiChar::Minus), "--key=value");
+        assert_eq!(s.trim_start_matches(empty), s);
+        assert_eq!(s.trim_end_matches(dashes), "--key=value");
+        assert_eq!(
+            "xyxyx"
+                .as_ascii_str()
+                .unwrap()
+                .trim_end_matches("xy".as_ascii_str().unwrap()),
+            "xyxyx"
+        );
+        assert_eq!(s.trim_matches(|_| true), "");
+
+        assert!(s.starts_with_ascii_str(dashes));
+        assert!(s.ends_with_ascii_str(dashes));
+        assert!(!dashes.starts_with_ascii_str(s));
+        assert!(!dashes.ends_with_ascii_str(s));
+        assert_eq!(s.strip_prefix_ascii_str(empty), Some(s));
+        assert_eq!(s.strip_suffix_ascii_str(empty), Some(s));
+        assert_eq!(s.strip_prefix_ascii_str(s), Some(empty));
+        assert_eq!(s.strip_suffix_ascii_str(&s[1..]), Some(&s[..1]));
+        assert_eq!(s.strip_prefix_ascii_str(&s[1..]), None);
+    }
+
+    #[test]
+    fn ignore_ascii_case() {
+        let s = "Content-Type: Text/HTML".as_ascii_str().unwrap();
+        let empty = "".as_ascii_str().unwrap();
+        let html = "text/html".as_ascii_str().unwrap();
+        assert_eq!(s.find_ignore_ascii_case(html), Some(14));
+        assert_eq!(
+            s.find_ignore_ascii_case("TYPE".as_ascii_str().unwrap()),
+            Some(8)
+        );
+        assert_eq!(
+            s.find_ignore_ascii_case("xml".as_ascii_str().unwrap()),
+            None
+        );
+        assert_eq!(s.find_ignore_ascii_case(empty), Some(0));
+        assert_eq!(html.find_ignore_ascii_case(s), None);
+        assert!(s.starts_with_ignore_ascii_case("content".as_ascii_str().unwrap()));
+        assert!(s.starts_with_ignore_ascii_case(empty));
+        assert!(!html.starts_with_ignore_ascii_case(s));
+        assert!(s.ends_with_ignore_ascii_case(html));
+        assert!(s.ends_with_ignore_ascii_case(empty));
+        assert!(!html.ends_with_ignore_ascii_case(s));
+        assert!(!s.ends_with_ignore_ascii_case("type".as_ascii_str().unwrap()));
+        assert_eq!(
+            s.strip_prefix_ignore_ascii_case("CONTENT-type: ".as_ascii_str().unwrap()),
+            Some(&s[14..])
+        );
+        assert_eq!(s.strip_prefix_ignore_ascii_case(s), Some(empty));
+        assert_eq!(s.strip_prefix_ignore_ascii_case(html), None);
+
+        let words = ["", "a", "A", "ab", "aB", "b", "_", "[", "Z", "z", "zz"];
+        for a in &words {
+            for b in &words {
+                let expected = a.to_ascii_lowercase().cmp(&b.to_ascii_lowercase());
+                let a = a.as_ascii_str().unwrap();
+                let b = b.as_ascii_str().unwrap();
+                assert_eq!(a.cmp_ignore_ascii_case(b), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn find_patterns() {
+        let s = "GET /index.html HTTP/1.1".as_ascii_str().unwrap();
+        let http = "HTTP".as_ascii_str().unwrap();
+        let empty = "".as_ascii_str().unwrap();
+        assert_eq!(s.find(AsciiChar::Slash), Some(4));
+        assert_eq!(s.rfind(AsciiChar::Slash), Some(20));
+        assert_eq!(s.find(http), Some(16));
+        assert_eq!(s.rfind(http), Some(16));
+        assert_eq!(s.find(empty), Some(0));
+        assert_eq!(s.rfind(empty), Some(s.len()));
+        assert_eq!(s.find(&[AsciiChar::Dot, AsciiChar::Space][..]), Some(3));
+        assert_eq!(s.rfind([AsciiChar::Dot, AsciiChar::Space]), Some(22));
+        assert_eq!(s.find(AsciiChar::is_lowercase), Some(5));
+        assert_eq!(s.rfind(|ch: AsciiChar| ch.is_ascii_digit()), Some(23));
+        assert_eq!(s.find(AsciiChar::Tab), None);
+        assert!(s.contains(AsciiChar::Dot));
+        assert!(!s.contains("http".as_ascii_str().unwrap()));
+        assert!(empty.contains(empty));
+        assert_eq!(empty.find(AsciiChar::Space), None);
+        assert_eq!(http.find(s), None);
+        // the returned indices can slice the haystack
+        let dot = s.find(AsciiChar::Dot).unwrap();
+        assert_eq!(&s[dot..dot + 5], ".html");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn match_indices() {
+        fn equals_str<'a>(
+            ascii: impl Iterator<Item = (usize, &'a AsciiStr)>,
+            std: impl Iterator<Item = (usize, &'a str)>,
+        ) {
+            let ascii = ascii.map(|(i, s)| (i, s.as_str())).collect::<Vec<_>>();
+            assert_eq!(ascii, std.collect::<Vec<_>>());
+        }
+        let haystack = "aaa,ab,,b";
+        let s = haystack.as_ascii_str().unwrap();
+        let aa = "aa".as_ascii_str().unwrap();
+        let empty = "".as_ascii_str().unwrap();
+        equals_str(s.match_indices(AsciiChar::a), haystack.match_indices('a'));
+        equals_str(s.rmatch_indices(AsciiChar::a), haystack.rmatch_indices('a'));
+        equals_str(s.match_indices(aa), haystack.match_indices("aa"));
+        equals_str(s.rmatch_indices(aa), haystack.rmatch_indices("aa"));
+        equals_str(s.match_indices(empty), haystack.match_indices(""));
+        equals_str(s.rmatch_indices(empty), haystack.rmatch_indices(""));
+        equals_str(empty.match_indices(empty), "".match_indices(""));
+        let commas = s.match_indices(AsciiChar::Comma).rev().map(|(i, _)| i);
+        assert_eq!(commas.collect::<Vec<_>>(), [7, 6, 3]);
+
+        let mut iter = s.matches(AsciiChar::is_alphabetic);
+        assert_eq!(iter.next(), Some("a".as_ascii_str().unwrap()));
+        assert_eq!(iter.next_back(), Some("b".as_ascii_str().unwrap()));
+        assert_eq!(iter.count(), 4);
+        assert_eq!(s.rmatches(aa).count(), 1);
+        assert_eq!(s.rmatches(AsciiChar::b).rev().count(), 2);
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn fmt_ascii_str() {
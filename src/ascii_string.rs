@@ -18,6 +18,11 @@ use std::ffi::{CStr, CString};
 
 use ascii_char::AsciiChar;
 use ascii_str::{AsAsciiStr, AsAsciiStrError, AsciiStr};
+use hex::{encode_hex_lower_to_slice, encode_hex_upper_to_slice};
+use lossy::LossyStrategy;
+#[cfg(feature = "transliteration")]
+use transliterate::{push_transliterated, TransliterationFallback};
+use validate::ascii_prefix_len;
 
 /// A growable string stored as an ASCII encoded buffer.
 #[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -161,6 +166,117 @@ impl AsciiString {
         }
     }
 
+    /// Converts a byte slice into an `AsciiString`, replacing every non-ASCII byte with
+    /// `replacement`.
+    ///
+    /// Multi-byte UTF-8 characters are replaced with one `replacement` per byte.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiString};
+    /// let s = AsciiString::from_ascii_lossy("caf\u{e9} 42\u{b0}C", AsciiChar::UnderScore);
+    /// assert_eq!(s, "caf__ 42__C");
+    /// let s = AsciiString::from_ascii_lossy(b"\xffOK", AsciiChar::Question);
+    /// assert_eq!(s, "?OK");
+    /// ```
+    #[must_use]
+    pub fn from_ascii_lossy<B>(bytes: &B, replacement: AsciiChar) -> AsciiString
+    where
+        B: AsRef<[u8]> + ?Sized,
+    {
+        AsciiString::from_ascii_lossy_with(bytes, LossyStrategy::Replace(replacement))
+    }
+
+    /// Converts a byte slice into an `AsciiString`, handling non-ASCII bytes as described
+    /// by `strategy`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiString, LossyStrategy};
+    /// let bytes = "Zürich\u{80}".as_bytes();
+    /// let lossy = |strategy| AsciiString::from_ascii_lossy_with(bytes, strategy);
+    /// assert_eq!(lossy(LossyStrategy::Replace(AsciiChar::Question)), "Z??rich??");
+    /// assert_eq!(lossy(LossyStrategy::Drop), "Zrich");
+    /// assert_eq!(lossy(LossyStrategy::CaretEscape), "ZM-CM-<richM-BM-^@");
+    /// assert_eq!(lossy(LossyStrategy::HexEscape), "Z\\xc3\\xbcrich\\xc2\\x80");
+    /// ```
+    #[must_use]
+    pub fn from_ascii_lossy_with<B>(bytes: &B, strategy: LossyStrategy) -> AsciiString
+    where
+        B: AsRef<[u8]> + ?Sized,
+    {
+        let bytes = bytes.as_ref();
+        let mut string = AsciiString::with_capacity(bytes.len());
+        string.push_bytes_lossy(bytes, strategy);
+        string
+    }
+
+    /// Converts Unicode text into an `AsciiString` by approximating non-ASCII characters,
+    /// and handling characters without an ASCII equivalent as described by `fallback`.
+    ///
+    /// See [`Transliterate`](crate::Transliterate) for how characters are approximated.
+    /// This method is only available with the `transliteration` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiString, TransliterationFallback};
+    /// let name = AsciiString::from_transliterated("José Müller", TransliterationFallback::Drop);
+    /// assert_eq!(name, "Jose Muller");
+    /// ```
+    #[cfg(feature = "transliteration")]
+    #[must_use]
+    pub fn from_transliterated(text: &str, fallback: TransliterationFallback) -> AsciiString {
+        let mut ascii = AsciiString::with_capacity(text.len());
+        for c in text.chars() {
+            push_transliterated(&mut ascii, c, fallback);
+        }
+        ascii
+    }
+
+    /// Encodes `bytes` as pairs of lowercase hex digits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiString;
+    /// assert_eq!(AsciiString::from_hex_lower(&[0xde, 0xad, 0x01]), "dead01");
+    /// ```
+    #[must_use]
+    pub fn from_hex_lower(bytes: &[u8]) -> AsciiString {
+        let mut hex = vec![AsciiChar::Null; bytes.len() * 2];
+        let _encoded = encode_hex_lower_to_slice(bytes, &mut hex);
+        AsciiString::from(hex)
+    }
+
+    /// Encodes `bytes` as pairs of uppercase hex digits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiString;
+    /// assert_eq!(AsciiString::from_hex_upper(&[0xde, 0xad, 0x01]), "DEAD01");
+    /// ```
+    #[must_use]
+    pub fn from_hex_upper(bytes: &[u8]) -> AsciiString {
+        let mut hex = vec![AsciiChar::Null; bytes.len() * 2];
+        let _encoded = encode_hex_upper_to_slice(bytes, &mut hex);
+        AsciiString::from(hex)
+    }
+
+    /// Appends `bytes`, handling non-ASCII bytes as described by `strategy`.
+    fn push_bytes_lossy(&mut self, mut bytes: &[u8], strategy: LossyStrategy) {
+        loop {
+            let (ascii, rest) = bytes.split_at(ascii_prefix_len(bytes));
+            // SAFETY: `ascii_prefix_len` guarantees that `ascii` only contains ascii bytes.
+            self.push_str(unsafe { ascii.as_ascii_str_unchecked() });
+            match rest.split_first() {
+                Some((&byte, rest)) => {
+                    strategy.replace(byte, |ch| self.push(ch));
+                    bytes = rest;
+                }
+                None => break,
+            }
+        }
+    }
+
     /// Pushes the given ASCII string onto this ASCII string buffer.
     ///
     /// # Examples
@@ -176,6 +292,48 @@ impl AsciiString {
         self.vec.extend(string.chars());
     }
 
+    /// Appends formatted text, failing if it contains a non-ASCII character.
+    ///
+    /// This is the method behind the [`write_ascii!`](crate::write_ascii) and
+    /// [`format_ascii!`](crate::format_ascii) macros.
+    /// Unlike `fmt::Write::write_fmt()`, the error tells which character wasn't ASCII and where
+    /// it would have been in the formatted text.
+    ///
+    /// # Errors
+    /// Returns `Err` if the formatted text contains a non-ASCII character.
+    /// The string is left unchanged in that case.
+    ///
+    /// # Panics
+    /// Panics if a formatting trait implementation returns an error, like `format!()` does.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiString;
+    /// let mut s = AsciiString::from_ascii("GET ").unwrap();
+    /// s.write_ascii_fmt(format_args!("/{} HTTP/1.{}", "index.html", 1)).unwrap();
+    /// assert_eq!(s, "GET /index.html HTTP/1.1");
+    ///
+    /// let error = s.write_ascii_fmt(format_args!("\r\nHost: {}", "bücher.example")).unwrap_err();
+    /// assert_eq!(error.offset(), 9);
+    /// assert_eq!(error.character(), 'ü');
+    /// assert_eq!(s, "GET /index.html HTTP/1.1");
+    /// ```
+    pub fn write_ascii_fmt(&mut self, args: fmt::Arguments) -> Result<(), NonAsciiFormatError> {
+        let start = self.len();
+        let mut writer = AsciiFormatWriter {
+            string: self,
+            start,
+            error: None,
+        };
+        let result = fmt::write(&mut writer, args);
+        if let Some(error) = writer.error {
+            self.truncate(start);
+            return Err(error);
+        }
+        result.expect("a formatting trait implementation returned an error");
+        Ok(())
+    }
+
     /// Inserts the given ASCII string at the given place in this ASCII string buffer.
     ///
     /// # Panics
@@ -419,6 +577,29 @@ impl AsciiString {
         let slice = self.vec.into_boxed_slice();
         Box::from(slice)
     }
+
+    /// Consumes the string and returns an iterator over its non-empty parts separated by
+    /// whitespace, as owned strings.
+    ///
+    /// Whitespace is determined the same way as for
+    /// [`AsciiStr::split_whitespace()`](struct.AsciiStr.html#method.split_whitespace).
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiString;
+    /// let line = AsciiString::from_ascii("  HELO  relay.example.org \r\n").unwrap();
+    /// let words: Vec<AsciiString> = line.split_whitespace_owned().collect();
+    /// assert_eq!(words, ["HELO", "relay.example.org"]);
+    /// ```
+    #[must_use]
+    pub fn split_whitespace_owned(self) -> SplitWhitespaceOwned {
+        let end = self.len();
+        SplitWhitespaceOwned {
+            string: self,
+            start: 0,
+            end,
+        }
+    }
 }
 
 impl Deref for AsciiString {
@@ -677,6 +858,39 @@ impl fmt::Write for AsciiString {
     }
 }
 
+/// The `fmt::Write` adapter used by `AsciiString::write_ascii_fmt()`,
+/// which remembers the first non-ASCII character instead of just failing.
+struct AsciiFormatWriter<'a> {
+    string: &'a mut AsciiString,
+    start: usize,
+    error: Option<NonAsciiFormatError>,
+}
+
+impl<'a> fmt::Write for AsciiFormatWriter<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        // A `Display` impl may ignore an error and keep writing,
+        // so only the first non-ASCII character is reported.
+        if self.error.is_some() {
+            return Err(fmt::Error);
+        }
+        match AsciiStr::from_ascii(s) {
+            Ok(astr) => {
+                self.string.push_str(astr);
+                Ok(())
+            }
+            Err(error) => {
+                let valid_up_to = error.valid_up_to();
+                let offset = self.string.len() - self.start + valid_up_to;
+                self.error = s
+                    .get(valid_up_to..)
+                    .and_then(|rest| rest.chars().next())
+                    .map(|character| NonAsciiFormatError { offset, character });
+                Err(fmt::Error)
+            }
+        }
+    }
+}
+
 impl<A: AsRef<AsciiStr>> FromIterator<A> for AsciiString {
     fn from_iter<I: IntoIterator<Item = A>>(iter: I) -> AsciiString {
         let mut buf = AsciiString::new();
@@ -737,6 +951,53 @@ where
     }
 }
 
+/// An iterator over the non-empty parts of an `AsciiString` separated by whitespace,
+/// which yields owned strings.
+///
+/// This type is created by [`AsciiString::split_whitespace_owned()`].
+#[derive(Clone, Debug)]
+pub struct SplitWhitespaceOwned {
+    string: AsciiString,
+    start: usize,
+    end: usize,
+}
+impl SplitWhitespaceOwned {
+    /// Returns the part of the string that hasn't been iterated over yet.
+    #[must_use]
+    pub fn as_str(&self) -> &AsciiStr {
+        // SAFETY: `start <= end <= string.len()` is upheld by `next()` and `next_back()`.
+        unsafe { self.string.as_slice().get_unchecked(self.start..self.end) }.into()
+    }
+}
+#[allow(clippy::indexing_slicing)] // The indices come from searching in `remaining`
+impl Iterator for SplitWhitespaceOwned {
+    type Item = AsciiString;
+
+    fn next(&mut self) -> Option<AsciiString> {
+        let remaining = self.as_str();
+        let from = remaining.find(|ch: AsciiChar| !ch.is_whitespace())?;
+        let to = remaining[from..]
+            .find(AsciiChar::is_whitespace)
+            .map_or(remaining.len(), |len| from + len);
+        let part = remaining[from..to].to_ascii_string();
+        self.start += to;
+        Some(part)
+    }
+}
+#[allow(clippy::indexing_slicing)] // The indices come from searching in `remaining`
+impl DoubleEndedIterator for SplitWhitespaceOwned {
+    fn next_back(&mut self) -> Option<AsciiString> {
+        let remaining = self.as_str();
+        let to = remaining.rfind(|ch: AsciiChar| !ch.is_whitespace())? + 1;
+        let from = remaining[..to]
+            .rfind(AsciiChar::is_whitespace)
+            .map_or(0, |at| at + 1);
+        let part = remaining[from..to].to_ascii_string();
+        self.end = self.start + from;
+        Some(part)
+    }
+}
+
 /// A possible error value when converting an `AsciiString` from a byte vector or string.
 /// It wraps an `AsAsciiStrError` which you can get through the `ascii_error()` method.
 ///
@@ -749,6 +1010,8 @@ where
 /// # use ascii::IntoAsciiString;
 /// let err = "bø!".to_string().into_ascii_string().unwrap_err();
 /// assert_eq!(err.ascii_error().valid_up_to(), 1);
+/// assert_eq!(err.non_ascii_char(), Some('ø'));
+/// assert_eq!(err.to_string(), "non-ASCII byte 0xC3 at offset 1");
 /// assert_eq!(err.into_source(), "bø!".to_string());
 /// ```
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -769,6 +1032,33 @@ impl<O> FromAsciiError<O> {
     pub fn into_source(self) -> O {
         self.owner
     }
+    /// Returns the index of the first non-ASCII byte,
+    /// like [`AsAsciiStrError::valid_up_to()`].
+    #[inline]
+    #[must_use]
+    pub fn valid_up_to(&self) -> usize {
+        self.error.valid_up_to()
+    }
+    /// Returns the first non-ASCII byte, like [`AsAsciiStrError::non_ascii_byte()`].
+    #[inline]
+    #[must_use]
+    pub fn non_ascii_byte(&self) -> Option<u8> {
+        self.error.non_ascii_byte()
+    }
+    /// Returns the character that starts with the first non-ASCII byte, if the input is valid
+    /// UTF-8 there, like [`AsAsciiStrError::non_ascii_char()`].
+    #[inline]
+    #[must_use]
+    pub fn non_ascii_char(&self) -> Option<char> {
+        self.error.non_ascii_char()
+    }
+    /// Returns the number of bytes to skip to get past the first non-ASCII byte,
+    /// as returned by [`AsAsciiStrError::error_len()`].
+    #[inline]
+    #[must_use]
+    pub fn error_len(&self) -> Option<usize> {
+        self.error.error_len()
+    }
 }
 impl<O> fmt::Debug for FromAsciiError<O> {
     #[inline]
@@ -795,6 +1085,61 @@ impl<O: Any> Error for FromAsciiError<O> {
     }
 }
 
+/// Error returned by [`format_ascii!`](crate::format_ascii), [`write_ascii!`](crate::write_ascii)
+/// and [`AsciiString::write_ascii_fmt()`] when the formatted text is not ASCII.
+///
+/// # Examples
+/// ```
+/// # #[macro_use] extern crate ascii;
+/// # fn main() {
+/// let user = "jürgen";
+/// let error = format_ascii!("USER {}\r\n", user).unwrap_err();
+/// assert_eq!(error.offset(), 6);
+/// assert_eq!(error.character(), 'ü');
+/// assert_eq!(
+///     error.to_string(),
+///     "non-ASCII character 'ü' at byte offset 6 of the formatted text"
+/// );
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NonAsciiFormatError {
+    offset: usize,
+    character: char,
+}
+
+impl NonAsciiFormatError {
+    /// Returns the byte offset of the non-ASCII character in the formatted text,
+    /// which is also the length of the formatted text before it.
+    #[inline]
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+    /// Returns the first non-ASCII character in the formatted text.
+    #[inline]
+    #[must_use]
+    pub const fn character(&self) -> char {
+        self.character
+    }
+}
+impl fmt::Display for NonAsciiFormatError {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            fmtr,
+            "non-ASCII character {:?} at byte offset {} of the formatted text",
+            self.character, self.offset
+        )
+    }
+}
+#[cfg(feature = "std")]
+impl Error for NonAsciiFormatError {
+    #[inline]
+    fn description(&self) -> &'static str {
+        "formatted text is not ASCII"
+    }
+}
+
 /// Convert vectors into `AsciiString`.
 pub trait IntoAsciiString: Sized {
     /// Convert to `AsciiString` without checking for non-ASCII characters.
@@ -894,10 +1239,13 @@ impl IntoAsciiString for CString {
 
     fn into_ascii_string(self) -> Result<AsciiString, FromAsciiError<Self>> {
         AsciiString::from_ascii(self.into_bytes_with_nul())
-            .map_err(|FromAsciiError { error, owner }| {
+            .map_err(|FromAsciiError { error, mut owner }| {
+                // `from_vec_unchecked()` adds the NULL byte back, reusing the capacity.
+                let nul = owner.pop();
+                debug_assert_eq!(nul, Some(0));
                 FromAsciiError {
-                    // SAFETY: We don't discard the NULL byte from the original
-                    //         string, so we ensure that it's null terminated
+                    // SAFETY: The bytes come from a `CString` without its NULL byte,
+                    //         so there are no interior NULL bytes.
                     owner: unsafe { CString::from_vec_unchecked(owner) },
                     error,
                 }
@@ -1020,6 +1368,8 @@ mod tests {
         let cstr = &*cstring;
         let ascii_err = cstr.into_ascii_string().unwrap_err();
         assert_eq!(ascii_err.into_source(), &*cstring);
+        let ascii_err = cstring.clone().into_ascii_string().unwrap_err();
+        assert_eq!(ascii_err.into_source(), cstring);
     }
 
     #[test]
@@ -1048,6 +1398,62 @@ mod tests {
         assert!(fmt::write(&mut s2, format_args!("{}", sparkle_heart)).is_err());
     }
 
+    #[test]
+    fn from_ascii_lossy() {
+        use alloc::borrow::Cow;
+        use {AsAsciiStr, LossyStrategy};
+
+        let all_bytes: Vec<u8> = (0..=255).collect();
+        let replaced = AsciiString::from_ascii_lossy(&all_bytes, AsciiChar::Question);
+        assert_eq!(replaced.len(), 256);
+        assert_eq!(&replaced[..128], all_bytes[..128].as_ascii_str().unwrap());
+        assert!(replaced[128..].chars().all(|ch| ch == AsciiChar::Question));
+
+        let dropped = AsciiString::from_ascii_lossy_with(&all_bytes, LossyStrategy::Drop);
+        assert_eq!(dropped, all_bytes[..128].as_ascii_str().unwrap());
+
+        let hex = AsciiString::from_ascii_lossy_with(&all_bytes, LossyStrategy::HexEscape);
+        assert_eq!(hex.len(), 128 + 128 * 4);
+        assert!(hex.as_str().ends_with("\\xfe\\xff"));
+        assert_eq!(&hex[128..136], "\\x80\\x81");
+
+        let caret = AsciiString::from_ascii_lossy_with(
+            &[0x80, 0x9f, 0xa0, 0xfe, 0xff][..],
+            LossyStrategy::CaretEscape,
+        );
+        assert_eq!(caret, "M-^@M-^_M- M-~M-^?");
+
+        assert_eq!(AsciiString::from_ascii_lossy("", AsciiChar::Question), "");
+        assert_eq!(
+            AsciiString::from_ascii_lossy("\u{1F600}", AsciiChar::Asterisk),
+            "****"
+        );
+        assert_eq!(
+            AsciiStr::from_ascii_lossy("ok", AsciiChar::Asterisk),
+            Cow::Borrowed("ok".as_ascii_str().unwrap())
+        );
+        assert_eq!(
+            AsciiStr::from_ascii_lossy("\u{e9}t\u{e9}", AsciiChar::e),
+            Cow::<AsciiStr>::Owned(AsciiString::from_ascii("eetee").unwrap())
+        );
+    }
+
+    #[test]
+    fn split_whitespace_owned() {
+        let s = AsciiString::from_ascii(" a\tbc  d\r\n").unwrap();
+        let mut iter = s.split_whitespace_owned();
+        assert_eq!(
+            iter.next_back(),
+            Some(AsciiString::from_ascii("d").unwrap())
+        );
+        assert_eq!(iter.as_str(), " a\tbc  ");
+        assert_eq!(iter.next(), Some(AsciiString::from_ascii("a").unwrap()));
+        assert_eq!(iter.next(), Some(AsciiString::from_ascii("bc").unwrap()));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(AsciiString::new().split_whitespace_owned().next(), None);
+    }
+
     #[test]
     fn to_and_from_box() {
         let string = "abc".into_ascii_string().unwrap();
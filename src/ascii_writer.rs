@@ -0,0 +1,292 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{self, Write};
+
+use ascii_array_string::AsciiArrayString;
+use ascii_char::AsciiChar;
+use ascii_str::{AsAsciiStr, AsciiStr};
+use chunks::{ascii_chunks, AsciiChunk};
+use lossy::LossyStrategy;
+
+/// A writer adapter that only lets ASCII through to the underlying writer.
+///
+/// In strict mode, created by [`new()`](#method.new), non-ASCII bytes are rejected with an
+/// [`io::Error`] of kind [`InvalidData`](io::ErrorKind::InvalidData) that wraps an
+/// [`AsAsciiStrError`](crate::AsAsciiStrError) whose
+/// [`valid_up_to()`](crate::AsAsciiStrError::valid_up_to) is the offset of the rejected byte
+/// among all bytes written to this `AsciiWriter`.
+///
+/// In lossy mode, created by [`lossy()`](#method.lossy), non-ASCII bytes are instead replaced
+/// as described by a [`LossyStrategy`], for example with `\xNN` escapes or with `?`.
+///
+/// Text that is already known to be ASCII can be written with
+/// [`write_ascii_str()`](#method.write_ascii_str), which skips validation.
+///
+/// # Examples
+/// ```
+/// # use std::io::Write;
+/// # use ascii::{AsciiWriter, LossyStrategy};
+/// let mut strict = AsciiWriter::new(Vec::new());
+/// strict.write_all(b"220 ready\r\n").unwrap();
+/// let error = strict.write_all("250 d\u{e9}j\u{e0} vu\r\n".as_bytes()).unwrap_err();
+/// assert_eq!(error.to_string(), "non-ASCII byte 0xC3 at offset 16");
+/// assert_eq!(strict.get_ref(), b"220 ready\r\n250 d");
+///
+/// let mut escaping = AsciiWriter::lossy(Vec::new(), LossyStrategy::HexEscape);
+/// escaping.write_all("250 d\u{e9}j\u{e0} vu\r\n".as_bytes()).unwrap();
+/// assert_eq!(escaping.get_ref(), b"250 d\\xc3\\xa9j\\xc3\\xa0 vu\r\n");
+/// ```
+pub struct AsciiWriter<W> {
+    inner: W,
+    /// What to do with non-ASCII bytes, or `None` to reject them.
+    strategy: Option<LossyStrategy>,
+    /// The number of bytes written to `self`.
+    position: u64,
+}
+
+impl<W: Write> AsciiWriter<W> {
+    /// Creates a new `AsciiWriter` that returns an error when asked to write a non-ASCII byte.
+    #[inline]
+    #[must_use]
+    pub fn new(inner: W) -> Self {
+        AsciiWriter {
+            inner,
+            strategy: None,
+            position: 0,
+        }
+    }
+
+    /// Creates a new `AsciiWriter` that replaces non-ASCII bytes as described by `strategy`.
+    ///
+    /// Writes through a lossy `AsciiWriter` only fail if the underlying writer does.
+    #[inline]
+    #[must_use]
+    pub fn lossy(inner: W, strategy: LossyStrategy) -> Self {
+        AsciiWriter {
+            inner,
+            strategy: Some(strategy),
+            position: 0,
+        }
+    }
+
+    /// Writes all of `s` to the underlying writer, without validating it again.
+    ///
+    /// # Errors
+    /// Returns any error from the underlying writer.
+    pub fn write_ascii_str(&mut self, s: &AsciiStr) -> io::Result<()> {
+        self.inner.write_all(s.as_bytes())?;
+        self.position += s.len() as u64;
+        Ok(())
+    }
+
+    /// Writes `ch` to the underlying writer.
+    ///
+    /// # Errors
+    /// Returns any error from the underlying writer.
+    pub fn write_ascii_char(&mut self, ch: AsciiChar) -> io::Result<()> {
+        self.write_ascii_str(ch.as_ref())
+    }
+
+    /// Writes the escaped form of `buf` and returns the number of bytes of `buf` consumed.
+    ///
+    /// An error from the underlying writer is only returned if no byte of `buf` was written,
+    /// or if it happened after only part of the replacement for a byte was written.
+    fn write_lossy(&mut self, buf: &[u8], strategy: LossyStrategy) -> io::Result<usize> {
+        let mut written = 0;
+        'chunks: for chunk in ascii_chunks(buf) {
+            match chunk {
+                AsciiChunk::Ascii(ascii) => match self.inner.write(ascii.as_bytes()) {
+                    Ok(n) => {
+                        written += n;
+                        if n < ascii.len() {
+                            break;
+                        }
+                    }
+                    Err(e) if written == 0 => return Err(e),
+                    Err(_) => break,
+                },
+                AsciiChunk::NonAscii(bytes) => {
+                    for &byte in bytes {
+                        let mut escaped = AsciiArrayString::<4>::new();
+                        strategy.replace(byte, |ch| escaped.push(ch));
+                        let escaped = escaped.as_bytes();
+                        match self.inner.write(escaped) {
+                            Ok(n) if n == escaped.len() => written += 1,
+                            Ok(0) => break 'chunks,
+                            Ok(n) => {
+                                // A partial replacement can't be undone, so finish it or fail.
+                                if let Err(e) = self.inner.write_all(escaped.split_at(n).1) {
+                                    self.position += written as u64;
+                                    return Err(e);
+                                }
+                                written += 1;
+                            }
+                            Err(e) if written == 0 => return Err(e),
+                            Err(_) => break 'chunks,
+                        }
+                    }
+                }
+            }
+        }
+        self.position += written as u64;
+        Ok(written)
+    }
+}
+
+impl<W> AsciiWriter<W> {
+    /// Returns the number of bytes written to this `AsciiWriter` so far.
+    ///
+    /// This is the offset that errors are relative to.
+    /// In lossy mode it counts the bytes before they were escaped.
+    #[inline]
+    #[must_use]
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Gets a reference to the underlying writer.
+    #[inline]
+    #[must_use]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing non-ASCII data directly to it is not prevented.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this `AsciiWriter`, returning the underlying writer.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for AsciiWriter<W> {
+    /// Writes the ASCII prefix of `buf`, or escapes `buf` in lossy mode.
+    ///
+    /// In strict mode a write that starts with a non-ASCII byte fails, and the returned
+    /// count never includes non-ASCII bytes.
+    /// In lossy mode the returned count is the number of bytes of `buf` that were written
+    /// before the underlying writer stopped accepting data.
+    /// If it stops in the middle of the replacement for a byte, an error is returned instead.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(strategy) = self.strategy {
+            return self.write_lossy(buf, strategy);
+        }
+        let ascii = ascii_prefix(buf, self.position)?;
+        let written = self.inner.write(ascii.as_bytes())?;
+        self.position += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Returns the ASCII prefix of `buf`, or an error for the non-ASCII byte it starts with.
+///
+/// `position` is the stream offset of `buf`, which is added to the offset in the error.
+/// Offsets that don't fit in an `AsAsciiStrError` are reported as `usize::MAX`.
+pub(crate) fn ascii_prefix(buf: &[u8], position: u64) -> io::Result<&AsciiStr> {
+    match buf.as_ascii_str() {
+        Ok(ascii) => Ok(ascii),
+        Err(error) if error.valid_up_to() == 0 => {
+            let error = error.with_offset(usize::try_from(position).unwrap_or(usize::MAX));
+            Err(io::Error::new(io::ErrorKind::InvalidData, error))
+        }
+        // SAFETY: `as_ascii_str()` checked the bytes before `valid_up_to`.
+        Err(error) => unsafe { Ok(buf.split_at(error.valid_up_to()).0.as_ascii_str_unchecked()) },
+    }
+}
+
+impl<W: fmt::Debug> fmt::Debug for AsciiWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AsciiWriter")
+            .field("inner", &self.inner)
+            .field("strategy", &self.strategy)
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AsciiWriter;
+    use std::io::{self, Write};
+    use {AsAsciiStrError, AsciiChar, AsciiStr, LossyStrategy};
+
+    fn escape(strategy: LossyStrategy, bytes: &[u8]) -> Vec<u8> {
+        let mut writer = AsciiWriter::lossy(Vec::new(), strategy);
+        writer.write_all(bytes).unwrap();
+        assert_eq!(writer.position(), bytes.len() as u64);
+        writer.into_inner()
+    }
+
+    #[test]
+    fn strict() {
+        let mut writer = AsciiWriter::new(Vec::new());
+        assert_eq!(writer.write(b"ok\xffno").unwrap(), 2);
+        let error = writer.write(b"\xffno").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let error: &AsAsciiStrError = error.get_ref().unwrap().downcast_ref().unwrap();
+        assert_eq!(error.valid_up_to(), 2);
+        assert_eq!(error.non_ascii_byte(), Some(0xff));
+
+        let text = AsciiStr::from_ascii(" fine").unwrap();
+        writer.write_ascii_str(text).unwrap();
+        writer.write_ascii_char(AsciiChar::Dot).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.position(), 8);
+        assert_eq!(writer.into_inner(), b"ok fine.");
+    }
+
+    #[test]
+    fn lossy() {
+        let input = "\u{e9}t\u{e9}\u{1}".as_bytes();
+        assert_eq!(
+            escape(LossyStrategy::HexEscape, input),
+            b"\\xc3\\xa9t\\xc3\\xa9\x01"
+        );
+        assert_eq!(
+            escape(LossyStrategy::CaretEscape, input),
+            b"M-CM-)tM-CM-)\x01"
+        );
+        let question = LossyStrategy::Replace(AsciiChar::Question);
+        assert_eq!(escape(question, input), b"??t??\x01");
+        assert_eq!(escape(LossyStrategy::Drop, input), b"t\x01");
+        assert_eq!(escape(LossyStrategy::Drop, b""), b"");
+    }
+
+    #[test]
+    fn lossy_partial_write() {
+        let mut out = [0; 6];
+        let mut writer = AsciiWriter::lossy(&mut out[..], LossyStrategy::HexEscape);
+        assert_eq!(writer.write(b"ab\xffcd").unwrap(), 3);
+        assert_eq!(writer.write(b"cd").unwrap(), 0);
+        assert_eq!(writer.position(), 3);
+        assert_eq!(out, *b"ab\\xff");
+
+        let mut out = [0; 2];
+        let mut writer = AsciiWriter::lossy(&mut out[..], LossyStrategy::HexEscape);
+        assert_eq!(writer.write(b"ab\xff").unwrap(), 2);
+        assert_eq!(writer.write(b"\xff").unwrap(), 0);
+        assert_eq!(writer.position(), 2);
+        assert_eq!(out, *b"ab");
+
+        // The sink fills up in the middle of an escape.
+        let mut out = [0; 4];
+        let mut writer = AsciiWriter::lossy(&mut out[..], LossyStrategy::HexEscape);
+        let error = writer.write(b"ab\xff").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+        assert_eq!(writer.position(), 2);
+        assert_eq!(out, *b"ab\\x");
+    }
+}
@@ -0,0 +1,102 @@
+//! Asynchronous ASCII line reading and writing for [`futures-io`](https://docs.rs/futures-io).
+//!
+//! Requires the `futures-io` feature.
+
+use futures_io::{AsyncBufRead, AsyncWrite};
+
+async_ascii_io!(AsyncBufRead, AsyncWrite, poll_close);
+
+#[cfg(test)]
+mod tests {
+    use super::{AsyncAsciiLines, AsyncAsciiWriter};
+    use async_io::test_util::{block_on, poll_ready};
+    use futures_io::{AsyncBufRead, AsyncRead, AsyncWrite};
+    use std::io;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use {AsAsciiStrError, AsciiStr};
+
+    /// An in-memory reader that returns at most three bytes per buffer.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl<'a> AsyncRead for Trickle<'a> {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.get_mut().0).poll_read(cx, buf)
+        }
+    }
+
+    impl<'a> AsyncBufRead for Trickle<'a> {
+        fn poll_fill_buf(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<&[u8]>> {
+            let data = self.get_mut().0;
+            Poll::Ready(Ok(&data[..data.len().min(3)]))
+        }
+
+        fn consume(self: Pin<&mut Self>, amt: usize) {
+            let this = self.get_mut();
+            this.0 = &this.0[amt..];
+        }
+    }
+
+    #[test]
+    fn lines() {
+        let input = b"GET / HTTP/1.1\r\nHost: localhost\n\nX-Bad: \xff\r\nX-Ok: 1";
+        let mut lines = AsyncAsciiLines::new(Trickle(input));
+        assert_eq!(
+            block_on(lines.next_line()).unwrap().unwrap(),
+            "GET / HTTP/1.1"
+        );
+        assert_eq!(
+            block_on(lines.next_line()).unwrap().unwrap(),
+            "Host: localhost"
+        );
+        assert_eq!(block_on(lines.next_line()).unwrap().unwrap(), "");
+        let error = block_on(lines.next_line()).unwrap_err();
+        let error: &AsAsciiStrError = error.get_ref().unwrap().downcast_ref().unwrap();
+        assert_eq!(error.valid_up_to(), 40);
+        assert_eq!(block_on(lines.next_line()).unwrap().unwrap(), "X-Ok: 1");
+        assert_eq!(block_on(lines.next_line()).unwrap(), None);
+        assert_eq!(lines.position(), input.len() as u64);
+    }
+
+    #[test]
+    fn character_split_across_buffers() {
+        let mut lines = AsyncAsciiLines::new(Trickle(b"ab\xc3\xa9\nok"));
+        let error = block_on(lines.next_line()).unwrap_err();
+        let error: &AsAsciiStrError = error.get_ref().unwrap().downcast_ref().unwrap();
+        assert_eq!(error.valid_up_to(), 2);
+        assert_eq!(error.non_ascii_char(), Some('é'));
+        assert_eq!(error.error_len(), Some(2));
+        assert_eq!(block_on(lines.next_line()).unwrap().unwrap(), "ok");
+    }
+
+    #[test]
+    fn max_line_length() {
+        let mut lines =
+            AsyncAsciiLines::with_max_line_length(6, &b"short\nway too long\nok\r\n"[..]);
+        assert_eq!(block_on(lines.next_line()).unwrap().unwrap(), "short");
+        let error = block_on(lines.next_line()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(block_on(lines.next_line()).unwrap().unwrap(), "ok");
+        assert_eq!(block_on(lines.next_line()).unwrap(), None);
+    }
+
+    #[test]
+    fn writer() {
+        let mut writer = AsyncAsciiWriter::new(Vec::new());
+        let greeting = AsciiStr::from_ascii("hello ").unwrap();
+        block_on(writer.write_ascii_str(greeting)).unwrap();
+        let written = poll_ready(|cx| Pin::new(&mut writer).poll_write(cx, b"w\xc3\xb6rld"));
+        assert_eq!(written.unwrap(), 1);
+        let error =
+            poll_ready(|cx| Pin::new(&mut writer).poll_write(cx, b"\xc3\xb6rld")).unwrap_err();
+        let error: &AsAsciiStrError = error.get_ref().unwrap().downcast_ref().unwrap();
+        assert_eq!(error.valid_up_to(), 7);
+        poll_ready(|cx| Pin::new(&mut writer).poll_close(cx)).unwrap();
+        assert_eq!(writer.position(), 7);
+        assert_eq!(writer.into_inner(), b"hello w");
+    }
+}
@@ -0,0 +1,487 @@
+use std::io;
+use std::mem;
+
+use ascii_char::AsciiChar;
+use ascii_reader::NonAsciiBytes;
+use ascii_str::AsAsciiStr;
+use ascii_string::AsciiString;
+
+/// Defines `AsyncAsciiLines`, `NextLine`, `AsyncAsciiWriter` and `WriteAsciiStr` for one async
+/// runtime, given its `AsyncBufRead` and `AsyncWrite` traits, which must be in scope,
+/// and the name of the method that closes an `AsyncWrite`.
+macro_rules! async_ascii_io {
+    ($bufread:ident, $write:ident, $close:ident) => {
+        use std::future::Future;
+        use std::io;
+        use std::pin::Pin;
+        use std::task::{Context, Poll};
+
+        use ascii_str::AsciiStr;
+        use ascii_string::AsciiString;
+        use ascii_writer::ascii_prefix;
+        use async_io::{Decoded, LineDecoder};
+
+        /// Reads the lines of an [`AsyncBufRead`] as [`AsciiString`]s.
+        ///
+        /// Lines are split the same way [`AsciiStr::lines()`] does: the `\n` or `\r\n` at the
+        /// end of each line is removed, and the last line doesn't need one.
+        ///
+        /// A line that contains a non-ASCII byte or is longer than the maximum line length is
+        /// reported as an error of kind [`InvalidData`](io::ErrorKind::InvalidData) as soon as
+        /// it's detected, and the rest of that line is then skipped.
+        /// For non-ASCII bytes the error wraps an [`AsAsciiStrError`](crate::AsAsciiStrError)
+        /// with the offset of the byte from the start of the stream, and a character that is
+        /// split across two buffers is only reported once the rest of it has been read.
+        ///
+        /// The reader must be [`Unpin`]; use [`Box::pin()`] for readers that aren't.
+        #[derive(Debug)]
+        pub struct AsyncAsciiLines<R> {
+            reader: R,
+            decoder: LineDecoder,
+        }
+
+        impl<R: $bufread + Unpin> AsyncAsciiLines<R> {
+            /// Creates a line reader without a limit on the line length.
+            #[inline]
+            #[must_use]
+            pub fn new(reader: R) -> Self {
+                Self::with_max_line_length(usize::MAX, reader)
+            }
+
+            /// Creates a line reader that rejects lines longer than `max_len` bytes,
+            /// including the line ending.
+            ///
+            /// This bounds how much memory a single line can use.
+            #[inline]
+            #[must_use]
+            pub fn with_max_line_length(max_len: usize, reader: R) -> Self {
+                AsyncAsciiLines {
+                    reader,
+                    decoder: LineDecoder::new(max_len),
+                }
+            }
+
+            /// Polls for the next line, which is `None` at the end of the stream.
+            ///
+            /// # Errors
+            /// Returns errors from the reader, and errors for invalid lines as described in
+            /// the [type-level documentation](AsyncAsciiLines).
+            pub fn poll_next_line(
+                &mut self,
+                cx: &mut Context,
+            ) -> Poll<io::Result<Option<AsciiString>>> {
+                loop {
+                    let (used, decoded) = {
+                        let available = match Pin::new(&mut self.reader).poll_fill_buf(cx) {
+                            Poll::Ready(Ok(available)) => available,
+                            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                            Poll::Pending => return Poll::Pending,
+                        };
+                        self.decoder.decode(available)
+                    };
+                    Pin::new(&mut self.reader).consume(used);
+                    match decoded {
+                        Decoded::NeedMore => {}
+                        Decoded::Line(line) => return Poll::Ready(line.map(Some)),
+                        Decoded::End => return Poll::Ready(Ok(None)),
+                    }
+                }
+            }
+
+            /// Returns a future that resolves to the next line, or `None` at the end of the stream.
+            #[inline]
+            pub fn next_line(&mut self) -> NextLine<'_, R> {
+                NextLine { lines: self }
+            }
+        }
+
+        impl<R> AsyncAsciiLines<R> {
+            /// Returns the number of bytes consumed from the reader so far.
+            #[inline]
+            #[must_use]
+            pub fn position(&self) -> u64 {
+                self.decoder.position()
+            }
+
+            /// Gets a reference to the underlying reader.
+            #[inline]
+            #[must_use]
+            pub fn get_ref(&self) -> &R {
+                &self.reader
+            }
+
+            /// Unwraps this `AsyncAsciiLines`, returning the underlying reader.
+            ///
+            /// Any part of a line that has been read but not returned is lost.
+            #[inline]
+            #[must_use]
+            pub fn into_inner(self) -> R {
+                self.reader
+            }
+        }
+
+        /// The future returned by [`AsyncAsciiLines::next_line()`].
+        #[derive(Debug)]
+        #[must_use = "futures do nothing unless polled"]
+        pub struct NextLine<'a, R> {
+            lines: &'a mut AsyncAsciiLines<R>,
+        }
+
+        impl<'a, R: $bufread + Unpin> Future for NextLine<'a, R> {
+            type Output = io::Result<Option<AsciiString>>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+                self.lines.poll_next_line(cx)
+            }
+        }
+
+        /// An [`AsyncWrite`] adapter that only lets ASCII through,
+        /// like [`AsciiWriter`](crate::AsciiWriter) does in strict mode.
+        ///
+        /// The writer must be [`Unpin`]; use [`Box::pin()`] for writers that aren't.
+        #[derive(Debug)]
+        pub struct AsyncAsciiWriter<W> {
+            inner: W,
+            /// The number of bytes written to `self`.
+            position: u64,
+        }
+
+        impl<W: $write + Unpin> AsyncAsciiWriter<W> {
+            /// Creates a new `AsyncAsciiWriter`.
+            #[inline]
+            #[must_use]
+            pub fn new(inner: W) -> Self {
+                AsyncAsciiWriter { inner, position: 0 }
+            }
+
+            /// Polls writing a prefix of `s` to the underlying writer, without validating it again.
+            ///
+            /// # Errors
+            /// Returns any error from the underlying writer.
+            pub fn poll_write_ascii_str(
+                &mut self,
+                cx: &mut Context,
+                s: &AsciiStr,
+            ) -> Poll<io::Result<usize>> {
+                let written = match Pin::new(&mut self.inner).poll_write(cx, s.as_bytes()) {
+                    Poll::Ready(Ok(written)) => written,
+                    other => return other,
+                };
+                self.position += written as u64;
+                Poll::Ready(Ok(written))
+            }
+
+            /// Returns a future that writes all of `s` to the underlying writer.
+            #[inline]
+            pub fn write_ascii_str<'a>(&'a mut self, s: &'a AsciiStr) -> WriteAsciiStr<'a, W> {
+                WriteAsciiStr {
+                    writer: self,
+                    remaining: s,
+                }
+            }
+        }
+
+        impl<W> AsyncAsciiWriter<W> {
+            /// Returns the number of bytes written to this `AsyncAsciiWriter` so far.
+            ///
+            /// This is the offset that errors are relative to.
+            #[inline]
+            #[must_use]
+            pub fn position(&self) -> u64 {
+                self.position
+            }
+
+            /// Gets a reference to the underlying writer.
+            #[inline]
+            #[must_use]
+            pub fn get_ref(&self) -> &W {
+                &self.inner
+            }
+
+            /// Unwraps this `AsyncAsciiWriter`, returning the underlying writer.
+            #[inline]
+            #[must_use]
+            pub fn into_inner(self) -> W {
+                self.inner
+            }
+        }
+
+        impl<W: $write + Unpin> $write for AsyncAsciiWriter<W> {
+            fn poll_write(
+                self: Pin<&mut Self>,
+                cx: &mut Context,
+                buf: &[u8],
+            ) -> Poll<io::Result<usize>> {
+                let this = self.get_mut();
+                match ascii_prefix(buf, this.position) {
+                    Ok(ascii) => this.poll_write_ascii_str(cx, ascii),
+                    Err(e) => Poll::Ready(Err(e)),
+                }
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+                Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+            }
+
+            fn $close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+                Pin::new(&mut self.get_mut().inner).$close(cx)
+            }
+        }
+
+        /// The future returned by [`AsyncAsciiWriter::write_ascii_str()`].
+        #[derive(Debug)]
+        #[must_use = "futures do nothing unless polled"]
+        pub struct WriteAsciiStr<'a, W> {
+            writer: &'a mut AsyncAsciiWriter<W>,
+            remaining: &'a AsciiStr,
+        }
+
+        impl<'a, W: $write + Unpin> Future for WriteAsciiStr<'a, W> {
+            type Output = io::Result<()>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+                let this = &mut *self;
+                while !this.remaining.is_empty() {
+                    let written = match this.writer.poll_write_ascii_str(cx, this.remaining) {
+                        Poll::Ready(Ok(0)) => {
+                            let error = io::Error::new(
+                                io::ErrorKind::WriteZero,
+                                "failed to write whole buffer",
+                            );
+                            return Poll::Ready(Err(error));
+                        }
+                        Poll::Ready(Ok(written)) => written,
+                        Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                        Poll::Pending => return Poll::Pending,
+                    };
+                    this.remaining = &this.remaining[written..];
+                }
+                Poll::Ready(Ok(()))
+            }
+        }
+    };
+}
+
+/// What [`LineDecoder::decode()`] found in the bytes it was given.
+pub(crate) enum Decoded {
+    /// The line is not complete yet.
+    NeedMore,
+    /// A complete line, or an error for a line that is then skipped.
+    Line(io::Result<AsciiString>),
+    /// The end of the stream was reached and there are no more lines.
+    End,
+}
+
+/// The reader-independent part of `AsyncAsciiLines`,
+/// which splits the buffers of an `AsyncBufRead` into lines.
+#[derive(Debug)]
+pub(crate) struct LineDecoder {
+    line: AsciiString,
+    /// The number of bytes of the current line that have been consumed,
+    /// including any bytes that were skipped.
+    line_len: usize,
+    max_line_len: usize,
+    /// The number of bytes consumed from the reader.
+    position: u64,
+    /// Whether the rest of the current line is discarded because of an error.
+    skipping: bool,
+    /// A non-ASCII character at the end of the last buffer, which is reported once the rest of
+    /// it has been read.
+    pending: Option<NonAsciiBytes>,
+}
+
+impl LineDecoder {
+    pub(crate) fn new(max_line_len: usize) -> Self {
+        LineDecoder {
+            line: AsciiString::new(),
+            line_len: 0,
+            max_line_len,
+            position: 0,
+            skipping: false,
+            pending: None,
+        }
+    }
+
+    pub(crate) fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Decodes as much of `available` as needed to complete a line,
+    /// and returns the number of bytes the reader should consume.
+    ///
+    /// An empty `available` means the end of the stream.
+    pub(crate) fn decode(&mut self, available: &[u8]) -> (usize, Decoded) {
+        if available.is_empty() {
+            let skipped = mem::replace(&mut self.skipping, false);
+            if let Some(pending) = self.pending.take() {
+                return (0, Decoded::Line(Err(pending.into_error())));
+            }
+            if skipped || self.line_len == 0 {
+                return (0, Decoded::End);
+            }
+            self.line_len = 0;
+            return (0, Decoded::Line(Ok(mem::take(&mut self.line))));
+        }
+
+        let (chunk, complete) = match available.iter().position(|&b| b == b'\n') {
+            Some(newline) => (available.split_at(newline + 1).0, true),
+            None => (available, false),
+        };
+        let offset = self.position;
+        self.position += chunk.len() as u64;
+        if self.skipping {
+            self.skipping = !complete;
+            if let Some(mut pending) = self.pending.take() {
+                pending.append(chunk);
+                if complete || pending.is_complete() {
+                    return (chunk.len(), Decoded::Line(Err(pending.into_error())));
+                }
+                self.pending = Some(pending);
+            }
+            return (chunk.len(), Decoded::NeedMore);
+        }
+
+        self.line_len += chunk.len();
+        let error = if self.line_len > self.max_line_len {
+            Some(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "line is longer than the maximum line length",
+            ))
+        } else {
+            match chunk.as_ascii_str() {
+                Ok(ascii) => {
+                    self.line.push_str(ascii);
+                    None
+                }
+                Err(error) => {
+                    let pending = NonAsciiBytes::new(offset, chunk, error);
+                    if !complete && !pending.is_complete() {
+                        self.line.clear();
+                        self.line_len = 0;
+                        self.skipping = true;
+                        self.pending = Some(pending);
+                        return (chunk.len(), Decoded::NeedMore);
+                    }
+                    Some(pending.into_error())
+                }
+            }
+        };
+        if let Some(error) = error {
+            self.line.clear();
+            self.line_len = 0;
+            self.skipping = !complete;
+            return (chunk.len(), Decoded::Line(Err(error)));
+        }
+        if !complete {
+            return (chunk.len(), Decoded::NeedMore);
+        }
+
+        let mut line = mem::take(&mut self.line);
+        self.line_len = 0;
+        let _lf = line.pop();
+        if line.last() == Some(AsciiChar::CarriageReturn) {
+            let _cr = line.pop();
+        }
+        (chunk.len(), Decoded::Line(Ok(line)))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::ptr;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    fn noop_waker() -> Waker {
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(ptr::null(), &VTABLE)
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        // SAFETY: The vtable functions don't use the data pointer.
+        unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+    }
+
+    /// Calls `poll` until it is ready, which it must become without needing to be woken.
+    pub(crate) fn poll_ready<T, F: FnMut(&mut Context) -> Poll<T>>(mut poll: F) -> T {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        for _ in 0..100 {
+            if let Poll::Ready(output) = poll(&mut cx) {
+                return output;
+            }
+        }
+        panic!("still pending after 100 polls");
+    }
+
+    pub(crate) fn block_on<F: Future + Unpin>(mut future: F) -> F::Output {
+        poll_ready(|cx| Pin::new(&mut future).poll(cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decoded, LineDecoder};
+    use std::io;
+    use AsAsciiStrError;
+
+    fn line(decoded: Decoded) -> io::Result<String> {
+        match decoded {
+            Decoded::Line(line) => line.map(|line| line.to_string()),
+            Decoded::NeedMore => panic!("expected a line, got NeedMore"),
+            Decoded::End => panic!("expected a line, got End"),
+        }
+    }
+
+    #[test]
+    fn decode() {
+        let mut lines = LineDecoder::new(8);
+        let (used, decoded) = lines.decode(b"ab\r\ncd");
+        assert_eq!((used, line(decoded).unwrap()), (4, "ab".to_string()));
+        let (used, decoded) = lines.decode(b"cd");
+        assert_eq!(used, 2);
+        assert!(matches!(decoded, Decoded::NeedMore));
+        let (used, decoded) = lines.decode(b"e\r");
+        assert_eq!(used, 2);
+        assert!(matches!(decoded, Decoded::NeedMore));
+        let (used, decoded) = lines.decode(b"");
+        assert_eq!((used, line(decoded).unwrap()), (0, "cde\r".to_string()));
+        assert!(matches!(lines.decode(b"").1, Decoded::End));
+        assert_eq!(lines.position(), 8);
+    }
+
+    #[test]
+    fn decode_errors() {
+        let mut decoder = LineDecoder::new(4);
+        let error = line(decoder.decode(b"abcde").1).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(decoder.decode(b"fg\nok").1, Decoded::NeedMore));
+        assert_eq!(line(decoder.decode(b"ok\n").1).unwrap(), "ok");
+
+        assert!(line(decoder.decode(b"\xff\n").1).is_err());
+        assert_eq!(line(decoder.decode(b"\n").1).unwrap(), "");
+        assert!(line(decoder.decode(b"x\xff").1).is_err());
+        assert!(matches!(decoder.decode(b"").1, Decoded::End));
+    }
+
+    #[test]
+    fn decode_split_character() {
+        let mut decoder = LineDecoder::new(usize::MAX);
+        assert!(matches!(decoder.decode(b"ab\xc3").1, Decoded::NeedMore));
+        let error = line(decoder.decode(b"\xa9\n").1).unwrap_err();
+        let error: &AsAsciiStrError = error.get_ref().unwrap().downcast_ref().unwrap();
+        assert_eq!(error.valid_up_to(), 2);
+        assert_eq!(error.non_ascii_char(), Some('é'));
+        assert_eq!(error.error_len(), Some(2));
+        assert_eq!(line(decoder.decode(b"ok\n").1).unwrap(), "ok");
+
+        // The stream ends in the middle of a character.
+        assert!(matches!(decoder.decode(b"\xe2\x82").1, Decoded::NeedMore));
+        let error = line(decoder.decode(b"").1).unwrap_err();
+        let error: &AsAsciiStrError = error.get_ref().unwrap().downcast_ref().unwrap();
+        assert_eq!(error.valid_up_to(), 8);
+        assert_eq!(error.non_ascii_char(), None);
+        assert!(matches!(decoder.decode(b"").1, Decoded::End));
+    }
+}
@@ -0,0 +1,64 @@
+//! Asynchronous ASCII line reading and writing for [`tokio`](https://docs.rs/tokio).
+//!
+//! Requires the `tokio` feature.
+
+use tokio::io::{AsyncBufRead, AsyncWrite};
+
+async_ascii_io!(AsyncBufRead, AsyncWrite, poll_shutdown);
+
+#[cfg(test)]
+mod tests {
+    use super::{AsyncAsciiLines, AsyncAsciiWriter};
+    use async_io::test_util::{block_on, poll_ready};
+    use std::io;
+    use std::pin::Pin;
+    use tokio::io::{duplex, AsyncWrite, BufReader};
+    use {AsAsciiStrError, AsciiStr};
+
+    #[test]
+    fn duplex_lines() {
+        let (client, server) = duplex(64);
+        let mut writer = AsyncAsciiWriter::new(client);
+        let request = AsciiStr::from_ascii("EHLO a\r\nRCPT TO:<b>\n").unwrap();
+        block_on(writer.write_ascii_str(request)).unwrap();
+        let error = poll_ready(|cx| Pin::new(&mut writer).poll_write(cx, b"\xe9\n")).unwrap_err();
+        let error: &AsAsciiStrError = error.get_ref().unwrap().downcast_ref().unwrap();
+        assert_eq!(error.valid_up_to(), 20);
+        let written = poll_ready(|cx| Pin::new(&mut writer).poll_write(cx, b"NOOP\r\n\xe9"));
+        assert_eq!(written.unwrap(), 6);
+        poll_ready(|cx| Pin::new(&mut writer).poll_shutdown(cx)).unwrap();
+
+        let mut lines = AsyncAsciiLines::new(BufReader::with_capacity(4, server));
+        assert_eq!(block_on(lines.next_line()).unwrap().unwrap(), "EHLO a");
+        assert_eq!(block_on(lines.next_line()).unwrap().unwrap(), "RCPT TO:<b>");
+        assert_eq!(block_on(lines.next_line()).unwrap().unwrap(), "NOOP");
+        assert_eq!(block_on(lines.next_line()).unwrap(), None);
+        assert_eq!(lines.position(), 26);
+    }
+
+    #[test]
+    fn duplex_errors() {
+        let (mut client, server) = duplex(64);
+        let input = b"abcdefgh\nijk\xc3\xa9\nlm\n";
+        assert_eq!(
+            poll_ready(|cx| Pin::new(&mut client).poll_write(cx, input)).unwrap(),
+            18
+        );
+        drop(client);
+
+        let mut lines =
+            AsyncAsciiLines::with_max_line_length(6, BufReader::with_capacity(3, server));
+        let error = block_on(lines.next_line()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error
+            .get_ref()
+            .unwrap()
+            .downcast_ref::<AsAsciiStrError>()
+            .is_none());
+        let error = block_on(lines.next_line()).unwrap_err();
+        let error: &AsAsciiStrError = error.get_ref().unwrap().downcast_ref().unwrap();
+        assert_eq!(error.valid_up_to(), 12);
+        assert_eq!(block_on(lines.next_line()).unwrap().unwrap(), "lm");
+        assert_eq!(block_on(lines.next_line()).unwrap(), None);
+    }
+}
@@ -0,0 +1,207 @@
+use core::iter::FusedIterator;
+
+use ascii_str::AsciiStr;
+
+/// Returns an iterator that splits `bytes` into maximal runs of ASCII and non-ASCII bytes.
+///
+/// This is the ASCII counterpart of [`Utf8Chunks`](https://doc.rust-lang.org/std/str/struct.Utf8Chunks.html):
+/// the chunks alternate between [`AsciiChunk::Ascii`] and [`AsciiChunk::NonAscii`],
+/// no chunk is empty, and concatenating them gives back the original bytes.
+///
+/// # Examples
+/// ```
+/// # use ascii::{ascii_chunks, AsciiChunk};
+/// let mut dump = String::new();
+/// for chunk in ascii_chunks(b"GET \xde\xad\xbe\xef HTTP/1.1") {
+///     match chunk {
+///         AsciiChunk::Ascii(text) => dump.push_str(text.as_str()),
+///         AsciiChunk::NonAscii(bytes) => {
+///             for b in bytes {
+///                 dump.push_str(&format!("<{:02x}>", b));
+///             }
+///         }
+///     }
+/// }
+/// assert_eq!(dump, "GET <de><ad><be><ef> HTTP/1.1");
+/// ```
+pub fn ascii_chunks<B: AsRef<[u8]> + ?Sized>(bytes: &B) -> AsciiChunks<'_> {
+    AsciiChunks {
+        bytes: bytes.as_ref(),
+    }
+}
+
+/// A maximal run of either ASCII or non-ASCII bytes.
+///
+/// This enum is yielded by [`AsciiChunks`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AsciiChunk<'a> {
+    /// A run of ASCII characters.
+    Ascii(&'a AsciiStr),
+    /// A run of bytes that are all outside the ASCII range.
+    NonAscii(&'a [u8]),
+}
+
+impl<'a> AsciiChunk<'a> {
+    /// Returns the bytes of this chunk, regardless of whether they are ASCII.
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        match *self {
+            AsciiChunk::Ascii(ascii) => ascii.as_bytes(),
+            AsciiChunk::NonAscii(bytes) => bytes,
+        }
+    }
+
+    /// Returns the ASCII run, or `None` if this chunk is not ASCII.
+    #[inline]
+    #[must_use]
+    pub fn ascii(&self) -> Option<&'a AsciiStr> {
+        match *self {
+            AsciiChunk::Ascii(ascii) => Some(ascii),
+            AsciiChunk::NonAscii(_) => None,
+        }
+    }
+
+    /// Returns the non-ASCII run, or `None` if this chunk is ASCII.
+    #[inline]
+    #[must_use]
+    pub fn non_ascii(&self) -> Option<&'a [u8]> {
+        match *self {
+            AsciiChunk::Ascii(_) => None,
+            AsciiChunk::NonAscii(bytes) => Some(bytes),
+        }
+    }
+
+    /// Returns `true` if this chunk is an ASCII run.
+    #[inline]
+    #[must_use]
+    pub fn is_ascii(&self) -> bool {
+        match *self {
+            AsciiChunk::Ascii(_) => true,
+            AsciiChunk::NonAscii(_) => false,
+        }
+    }
+
+    /// Returns the number of bytes in this chunk.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    /// Returns `true` if this chunk has no bytes, which is never the case for chunks
+    /// yielded by [`AsciiChunks`].
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.as_bytes().is_empty()
+    }
+}
+
+/// An iterator over the maximal ASCII and non-ASCII runs of a byte slice.
+///
+/// This struct is created by [`ascii_chunks()`].
+#[derive(Clone, Debug)]
+pub struct AsciiChunks<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> AsciiChunks<'a> {
+    /// Returns the bytes that have not been yielded yet.
+    #[inline]
+    #[must_use]
+    pub fn remainder(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl<'a> Iterator for AsciiChunks<'a> {
+    type Item = AsciiChunk<'a>;
+
+    fn next(&mut self) -> Option<AsciiChunk<'a>> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let valid_up_to = match AsciiStr::from_ascii(self.bytes) {
+            Ok(ascii) => {
+                self.bytes = &[];
+                return Some(AsciiChunk::Ascii(ascii));
+            }
+            Err(error) => error.valid_up_to(),
+        };
+        if valid_up_to == 0 {
+            let len = self
+                .bytes
+                .iter()
+                .position(u8::is_ascii)
+                .unwrap_or(self.bytes.len());
+            let (non_ascii, rest) = self.bytes.split_at(len);
+            self.bytes = rest;
+            Some(AsciiChunk::NonAscii(non_ascii))
+        } else {
+            let (ascii, rest) = self.bytes.split_at(valid_up_to);
+            self.bytes = rest;
+            // SAFETY: `from_ascii()` checked that all bytes before `valid_up_to` are ASCII.
+            Some(AsciiChunk::Ascii(unsafe {
+                AsciiStr::from_ascii_unchecked(ascii)
+            }))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.bytes.is_empty() {
+            (0, Some(0))
+        } else {
+            (1, Some(self.bytes.len()))
+        }
+    }
+}
+
+impl<'a> FusedIterator for AsciiChunks<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{ascii_chunks, AsciiChunk};
+    use AsciiStr;
+
+    #[test]
+    fn chunks() {
+        assert_eq!(ascii_chunks("").next(), None);
+
+        let mut chunks = ascii_chunks("plain text");
+        let all = AsciiStr::from_ascii("plain text").unwrap();
+        assert_eq!(chunks.next(), Some(AsciiChunk::Ascii(all)));
+        assert_eq!(chunks.next(), None);
+
+        let mut chunks = ascii_chunks(b"\xff\xfeab\xc3\xa9c");
+        assert_eq!(chunks.size_hint(), (1, Some(7)));
+        assert_eq!(chunks.next(), Some(AsciiChunk::NonAscii(&b"\xff\xfe"[..])));
+        assert_eq!(chunks.remainder(), b"ab\xc3\xa9c");
+        let ab = chunks.next().unwrap();
+        assert!(ab.is_ascii());
+        assert_eq!(ab.ascii().map(AsciiStr::as_str), Some("ab"));
+        let e = chunks.next().unwrap();
+        assert_eq!(e.non_ascii(), Some(&b"\xc3\xa9"[..]));
+        assert_eq!(e.ascii(), None);
+        assert_eq!(chunks.next().map(|c| c.as_bytes()), Some(&b"c"[..]));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.next(), None);
+    }
+
+    #[test]
+    fn chunks_cover_input() {
+        let input = b"\x80x\x81\x82yz\x83 \xf0\x9f\x98\x80end";
+        let mut rebuilt = [0_u8; 15];
+        let mut offset = 0;
+        let mut last_was_ascii = None;
+        for chunk in ascii_chunks(&input[..]) {
+            assert!(!chunk.is_empty());
+            assert_ne!(last_was_ascii, Some(chunk.is_ascii()));
+            last_was_ascii = Some(chunk.is_ascii());
+            rebuilt[offset..offset + chunk.len()].copy_from_slice(chunk.as_bytes());
+            offset += chunk.len();
+        }
+        assert_eq!(offset, input.len());
+        assert_eq!(&rebuilt[..], &input[..]);
+    }
+}
@@ -0,0 +1,873 @@
+use alloc::vec::Vec;
+use core::borrow::{Borrow, BorrowMut};
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::mem::{self, ManuallyDrop};
+use core::ops::{Add, AddAssign, Deref, DerefMut};
+use core::str::FromStr;
+use core::{fmt, slice};
+
+use ascii_char::AsciiChar;
+use ascii_str::{AsAsciiStr, AsAsciiStrError, AsciiStr};
+use ascii_string::AsciiString;
+
+/// The pointer, length and capacity of a heap-allocated string, like in a `Vec`.
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct Heap {
+    ptr: *mut AsciiChar,
+    len: usize,
+    /// Stored in little-endian byte order, so that its most significant byte is the last byte of
+    /// the representation on all targets. That byte never has the high bit set, as allocations
+    /// can't be larger than `isize::MAX`.
+    capacity_le: usize,
+}
+
+/// The number of characters a `CompactAsciiString` can store without allocating.
+const INLINE_CAPACITY: usize = mem::size_of::<Heap>() - 1;
+
+/// Set in `Inline::tag` to tell an inline string from a heap-allocated one.
+const INLINE_TAG: u8 = 0x80;
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct Inline {
+    chars: [AsciiChar; INLINE_CAPACITY],
+    /// `INLINE_TAG` combined with the length.
+    tag: u8,
+}
+
+/// `tag` overlaps with the most significant byte of `Heap::capacity_le`.
+#[repr(C)]
+union Repr {
+    inline: Inline,
+    heap: Heap,
+}
+
+/// A growable ASCII string that stores short strings inline instead of on the heap.
+///
+/// A `CompactAsciiString` is the same size as an [`AsciiString`], three `usize`s,
+/// and uses all but one of those bytes to store up to 23 characters (11 on 32-bit targets)
+/// without allocating.
+/// As ASCII characters never have the high bit set, the last byte can tell the two
+/// representations apart: for an inline string it holds the length with the high bit set,
+/// and for a heap-allocated one it is the most significant byte of the capacity.
+///
+/// Longer strings are moved to the heap and behave like an `AsciiString`.
+/// Converting from an `AsciiString` reuses its allocation, and so does converting a
+/// heap-allocated `CompactAsciiString` back.
+///
+/// # Examples
+/// ```
+/// # use ascii::{AsciiStr, AsciiString, CompactAsciiString};
+/// let mut id = CompactAsciiString::from_ascii("user_id").unwrap();
+/// assert!(id.is_inline());
+/// id.push_str(AsciiStr::from_ascii("_that_is_rather_long").unwrap());
+/// assert!(!id.is_inline());
+/// assert_eq!(id, "user_id_that_is_rather_long");
+///
+/// let owned: AsciiString = id.into();
+/// assert_eq!(owned, "user_id_that_is_rather_long");
+/// ```
+pub struct CompactAsciiString {
+    repr: Repr,
+}
+
+// SAFETY: A `CompactAsciiString` owns its heap allocation, like an `AsciiString` does.
+unsafe impl Send for CompactAsciiString {}
+// SAFETY: Shared references only allow reading, like for an `AsciiString`.
+unsafe impl Sync for CompactAsciiString {}
+
+/// Writes a `Vec` back into the heap representation when dropped,
+/// even if the code modifying it panics.
+struct VecGuard<'a> {
+    vec: ManuallyDrop<Vec<AsciiChar>>,
+    heap: &'a mut Heap,
+}
+
+impl<'a> Drop for VecGuard<'a> {
+    fn drop(&mut self) {
+        *self.heap = Heap::from_vec(&mut self.vec);
+    }
+}
+
+impl<'a> Deref for VecGuard<'a> {
+    type Target = Vec<AsciiChar>;
+
+    fn deref(&self) -> &Vec<AsciiChar> {
+        &self.vec
+    }
+}
+
+impl<'a> DerefMut for VecGuard<'a> {
+    fn deref_mut(&mut self) -> &mut Vec<AsciiChar> {
+        &mut self.vec
+    }
+}
+
+impl Heap {
+    /// Takes over the buffer of `vec`, which must not be dropped afterwards.
+    fn from_vec(vec: &mut ManuallyDrop<Vec<AsciiChar>>) -> Heap {
+        Heap {
+            ptr: vec.as_mut_ptr(),
+            len: vec.len(),
+            capacity_le: vec.capacity().to_le(),
+        }
+    }
+
+    /// # Safety
+    /// `self` must come from `from_vec()`, and the returned `Vec` must not be dropped while
+    /// `self` is still used.
+    unsafe fn to_vec(self) -> ManuallyDrop<Vec<AsciiChar>> {
+        // SAFETY: The caller guarantees the parts come from a `Vec`.
+        ManuallyDrop::new(unsafe {
+            Vec::from_raw_parts(self.ptr, self.len, usize::from_le(self.capacity_le))
+        })
+    }
+}
+
+impl CompactAsciiString {
+    /// Creates a new, empty string, which doesn't allocate.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::CompactAsciiString;
+    /// const EMPTY: CompactAsciiString = CompactAsciiString::new();
+    /// assert!(EMPTY.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        CompactAsciiString {
+            repr: Repr {
+                inline: Inline {
+                    chars: [AsciiChar::Null; INLINE_CAPACITY],
+                    tag: INLINE_TAG,
+                },
+            },
+        }
+    }
+
+    /// Creates a new string that can hold at least `capacity` characters without reallocating.
+    ///
+    /// This only allocates if `capacity` is larger than what fits inline.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity <= INLINE_CAPACITY {
+            CompactAsciiString::new()
+        } else {
+            CompactAsciiString::from_vec(Vec::with_capacity(capacity))
+        }
+    }
+
+    /// Copies anything that can represent a byte buffer into a `CompactAsciiString`.
+    ///
+    /// # Errors
+    /// Returns `Err` if not all of the bytes are ASCII characters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::CompactAsciiString;
+    /// let s = CompactAsciiString::from_ascii(b"ok").unwrap();
+    /// assert_eq!(s, "ok");
+    /// let error = CompactAsciiString::from_ascii("n\u{e4}").unwrap_err();
+    /// assert_eq!(error.valid_up_to(), 1);
+    /// ```
+    pub fn from_ascii<B>(bytes: &B) -> Result<Self, AsAsciiStrError>
+    where
+        B: AsRef<[u8]> + ?Sized,
+    {
+        bytes.as_ref().as_ascii_str().map(CompactAsciiString::from)
+    }
+
+    fn from_vec(vec: Vec<AsciiChar>) -> Self {
+        CompactAsciiString {
+            repr: Repr {
+                heap: Heap::from_vec(&mut ManuallyDrop::new(vec)),
+            },
+        }
+    }
+
+    /// Returns `true` if the string is stored inline, and `false` if it is on the heap.
+    #[inline]
+    #[must_use]
+    pub fn is_inline(&self) -> bool {
+        // SAFETY: All bytes of both representations are initialized,
+        //         so the tag can always be read as an `u8`.
+        unsafe { self.repr.inline.tag & INLINE_TAG != 0 }
+    }
+
+    /// Returns the inline representation, or `None` if the string is on the heap.
+    #[inline]
+    fn inline_mut(&mut self) -> Option<&mut Inline> {
+        if self.is_inline() {
+            // SAFETY: We just checked that the string is stored inline.
+            Some(unsafe { &mut self.repr.inline })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the heap-allocated `Vec`, moving the string to the heap first if necessary.
+    fn vec_mut(&mut self) -> VecGuard<'_> {
+        if self.is_inline() {
+            let mut vec = Vec::with_capacity(2 * INLINE_CAPACITY);
+            vec.extend_from_slice(self.as_slice());
+            *self = CompactAsciiString::from_vec(vec);
+        }
+        // SAFETY: The string is on the heap now.
+        let heap = unsafe { &mut self.repr.heap };
+        VecGuard {
+            // SAFETY: The guard writes the `Vec` back into `heap` instead of dropping it.
+            vec: unsafe { heap.to_vec() },
+            heap,
+        }
+    }
+
+    /// Returns the contents as an `AsciiStr`.
+    #[inline]
+    #[must_use]
+    pub fn as_ascii_str(&self) -> &AsciiStr {
+        let slice = if self.is_inline() {
+            // SAFETY: The string is stored inline, where the length is at most `INLINE_CAPACITY`.
+            unsafe {
+                let inline = &self.repr.inline;
+                inline
+                    .chars
+                    .get_unchecked(..usize::from(inline.tag & !INLINE_TAG))
+            }
+        } else {
+            // SAFETY: The string is on the heap, where `ptr` points to `len` characters.
+            unsafe { slice::from_raw_parts(self.repr.heap.ptr, self.repr.heap.len) }
+        };
+        slice.into()
+    }
+
+    /// Returns the contents as a mutable `AsciiStr`.
+    #[inline]
+    #[must_use]
+    pub fn as_mut_ascii_str(&mut self) -> &mut AsciiStr {
+        let slice = if self.is_inline() {
+            // SAFETY: The string is stored inline, where the length is at most `INLINE_CAPACITY`.
+            unsafe {
+                let inline = &mut self.repr.inline;
+                let len = usize::from(inline.tag & !INLINE_TAG);
+                inline.chars.get_unchecked_mut(..len)
+            }
+        } else {
+            // SAFETY: The string is on the heap, where `ptr` points to `len` characters.
+            unsafe { slice::from_raw_parts_mut(self.repr.heap.ptr, self.repr.heap.len) }
+        };
+        slice.into()
+    }
+
+    /// Returns the number of characters in the string.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.as_ascii_str().len()
+    }
+
+    /// Returns `true` if the string contains no characters.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of characters the string can hold without reallocating.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::CompactAsciiString;
+    /// assert!(CompactAsciiString::new().capacity() >= 11);
+    /// assert!(CompactAsciiString::with_capacity(100).capacity() >= 100);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        if self.is_inline() {
+            INLINE_CAPACITY
+        } else {
+            // SAFETY: The string is on the heap.
+            usize::from_le(unsafe { self.repr.heap.capacity_le })
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more characters,
+    /// moving the string to the heap if they don't fit inline.
+    ///
+    /// # Panics
+    /// Panics if the new capacity overflows `usize`.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self
+            .len()
+            .checked_add(additional)
+            .expect("capacity overflow");
+        if required > self.capacity() {
+            self.vec_mut().reserve(additional);
+        }
+    }
+
+    /// Reserves capacity for exactly `additional` more characters,
+    /// moving the string to the heap if they don't fit inline.
+    ///
+    /// # Panics
+    /// Panics if the new capacity overflows `usize`.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        let required = self
+            .len()
+            .checked_add(additional)
+            .expect("capacity overflow");
+        if required > self.capacity() {
+            let len = self.len();
+            let mut vec = self.vec_mut();
+            vec.reserve_exact(required - len);
+        }
+    }
+
+    /// Shrinks the capacity to match the length,
+    /// moving the string back inline if it fits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::CompactAsciiString;
+    /// let mut s = CompactAsciiString::with_capacity(100);
+    /// assert!(!s.is_inline());
+    /// s.shrink_to_fit();
+    /// assert!(s.is_inline());
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        if self.is_inline() {
+            return;
+        }
+        if self.len() <= INLINE_CAPACITY {
+            *self = CompactAsciiString::from(self.as_ascii_str());
+        } else {
+            self.vec_mut().shrink_to_fit();
+        }
+    }
+
+    /// Appends a character to the end of the string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, CompactAsciiString};
+    /// let mut s = CompactAsciiString::new();
+    /// s.push(AsciiChar::O);
+    /// s.push(AsciiChar::K);
+    /// assert_eq!(s, "OK");
+    /// ```
+    #[inline]
+    pub fn push(&mut self, ch: AsciiChar) {
+        if let Some(inline) = self.inline_mut() {
+            let len = usize::from(inline.tag & !INLINE_TAG);
+            if let Some(slot) = inline.chars.get_mut(len) {
+                *slot = ch;
+                inline.tag += 1;
+                return;
+            }
+        }
+        self.vec_mut().push(ch);
+    }
+
+    /// Appends a string to the end of this string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiStr, CompactAsciiString};
+    /// let mut s = CompactAsciiString::from_ascii("foo").unwrap();
+    /// s.push_str(AsciiStr::from_ascii("bar").unwrap());
+    /// assert_eq!(s, "foobar");
+    /// ```
+    pub fn push_str(&mut self, string: &AsciiStr) {
+        if let Some(inline) = self.inline_mut() {
+            let len = usize::from(inline.tag & !INLINE_TAG);
+            if let Some(slots) = inline.chars.get_mut(len..len + string.len()) {
+                slots.copy_from_slice(string.as_slice());
+                #[allow(clippy::cast_possible_truncation)] // Fits inline, so it's below 128.
+                {
+                    inline.tag += string.len() as u8;
+                }
+                return;
+            }
+        }
+        self.vec_mut().extend_from_slice(string.as_slice());
+    }
+
+    /// Inserts a character at the given position.
+    ///
+    /// # Panics
+    /// Panics if `idx` is larger than the string's length.
+    #[inline]
+    pub fn insert(&mut self, idx: usize, ch: AsciiChar) {
+        self.insert_str(idx, AsciiStr::new(&[ch]));
+    }
+
+    /// Inserts a string at the given position.
+    ///
+    /// # Panics
+    /// Panics if `idx` is larger than the string's length.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiStr, CompactAsciiString};
+    /// let mut s = CompactAsciiString::from_ascii("abc").unwrap();
+    /// s.insert_str(1, AsciiStr::from_ascii("def").unwrap());
+    /// assert_eq!(s, "adefbc");
+    /// ```
+    pub fn insert_str(&mut self, idx: usize, string: &AsciiStr) {
+        assert!(idx <= self.len(), "insertion index is out of bounds");
+        self.push_str(string);
+        self.as_mut_slice()
+            .split_at_mut(idx)
+            .1
+            .rotate_right(string.len());
+    }
+
+    /// Shortens the string to `new_len` characters.
+    ///
+    /// Does nothing if `new_len` is greater than the current length.
+    /// This never moves the string between the heap and inline storage.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len >= self.len() {
+            return;
+        }
+        if let Some(inline) = self.inline_mut() {
+            #[allow(clippy::cast_possible_truncation)] // Smaller than the inline length.
+            {
+                inline.tag = INLINE_TAG | new_len as u8;
+            }
+        } else {
+            self.vec_mut().truncate(new_len);
+        }
+    }
+
+    /// Removes the last character from the string and returns it,
+    /// or `None` if the string is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<AsciiChar> {
+        let ch = self.last()?;
+        self.truncate(self.len() - 1);
+        Some(ch)
+    }
+
+    /// Removes the character at position `idx` and returns it.
+    ///
+    /// # Panics
+    /// Panics if `idx` is out of bounds.
+    pub fn remove(&mut self, idx: usize) -> AsciiChar {
+        assert!(idx < self.len(), "removal index is out of bounds");
+        self.as_mut_slice().split_at_mut(idx).1.rotate_left(1);
+        let ch = self.pop();
+        ch.expect("the string is not empty")
+    }
+
+    /// Removes all characters from the string.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Converts the string into an `AsciiString`.
+    ///
+    /// This only allocates if the string is stored inline.
+    #[must_use]
+    pub fn into_ascii_string(self) -> AsciiString {
+        if self.is_inline() {
+            return AsciiString::from(self.as_ascii_str());
+        }
+        let this = ManuallyDrop::new(self);
+        // SAFETY: The string is on the heap, and `this` won't be dropped.
+        let vec = unsafe { this.repr.heap.to_vec() };
+        AsciiString::from(ManuallyDrop::into_inner(vec))
+    }
+}
+
+impl Drop for CompactAsciiString {
+    fn drop(&mut self) {
+        if !self.is_inline() {
+            // SAFETY: The string is on the heap, and won't be used again.
+            unsafe { drop(ManuallyDrop::into_inner(self.repr.heap.to_vec())) };
+        }
+    }
+}
+
+impl Clone for CompactAsciiString {
+    /// Clones into inline storage if the string fits.
+    #[inline]
+    fn clone(&self) -> Self {
+        CompactAsciiString::from(self.as_ascii_str())
+    }
+}
+
+impl Default for CompactAsciiString {
+    #[inline]
+    fn default() -> Self {
+        CompactAsciiString::new()
+    }
+}
+
+impl Deref for CompactAsciiString {
+    type Target = AsciiStr;
+
+    #[inline]
+    fn deref(&self) -> &AsciiStr {
+        self.as_ascii_str()
+    }
+}
+
+impl DerefMut for CompactAsciiString {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut AsciiStr {
+        self.as_mut_ascii_str()
+    }
+}
+
+impl PartialEq for CompactAsciiString {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ascii_str() == other.as_ascii_str()
+    }
+}
+
+impl Eq for CompactAsciiString {}
+
+impl PartialOrd for CompactAsciiString {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CompactAsciiString {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_ascii_str().cmp(other.as_ascii_str())
+    }
+}
+
+impl Hash for CompactAsciiString {
+    /// Hashes the same way as `AsciiStr`, as required by `Borrow<AsciiStr>`.
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ascii_str().hash(state);
+    }
+}
+
+impl PartialEq<AsciiString> for CompactAsciiString {
+    #[inline]
+    fn eq(&self, other: &AsciiString) -> bool {
+        *self.as_ascii_str() == **other
+    }
+}
+
+impl PartialEq<AsciiStr> for CompactAsciiString {
+    #[inline]
+    fn eq(&self, other: &AsciiStr) -> bool {
+        self.as_ascii_str() == other
+    }
+}
+
+impl<'a> PartialEq<&'a AsciiStr> for CompactAsciiString {
+    #[inline]
+    fn eq(&self, other: &&'a AsciiStr) -> bool {
+        self.as_ascii_str() == *other
+    }
+}
+
+impl PartialEq<str> for CompactAsciiString {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        *self.as_ascii_str() == *other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for CompactAsciiString {
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {
+        *self.as_ascii_str() == **other
+    }
+}
+
+impl fmt::Display for CompactAsciiString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_ascii_str(), f)
+    }
+}
+
+impl fmt::Debug for CompactAsciiString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_ascii_str(), f)
+    }
+}
+
+impl fmt::Write for CompactAsciiString {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let string = s.as_ascii_str().map_err(|_| fmt::Error)?;
+        self.push_str(string);
+        Ok(())
+    }
+}
+
+impl FromStr for CompactAsciiString {
+    type Err = AsAsciiStrError;
+
+    fn from_str(s: &str) -> Result<CompactAsciiString, AsAsciiStrError> {
+        CompactAsciiString::from_ascii(s)
+    }
+}
+
+impl<A: AsRef<AsciiStr>> FromIterator<A> for CompactAsciiString {
+    fn from_iter<I: IntoIterator<Item = A>>(iter: I) -> CompactAsciiString {
+        let mut buf = CompactAsciiString::new();
+        buf.extend(iter);
+        buf
+    }
+}
+
+impl<A: AsRef<AsciiStr>> Extend<A> for CompactAsciiString {
+    fn extend<I: IntoIterator<Item = A>>(&mut self, iterable: I) {
+        let iterator = iterable.into_iter();
+        let (lower_bound, _) = iterator.size_hint();
+        self.reserve(lower_bound);
+        for item in iterator {
+            self.push_str(item.as_ref());
+        }
+    }
+}
+
+impl<'a> Add<&'a AsciiStr> for CompactAsciiString {
+    type Output = CompactAsciiString;
+
+    #[inline]
+    fn add(mut self, other: &AsciiStr) -> CompactAsciiString {
+        self.push_str(other);
+        self
+    }
+}
+
+impl<'a> AddAssign<&'a AsciiStr> for CompactAsciiString {
+    #[inline]
+    fn add_assign(&mut self, other: &AsciiStr) {
+        self.push_str(other);
+    }
+}
+
+impl AsRef<AsciiStr> for CompactAsciiString {
+    #[inline]
+    fn as_ref(&self) -> &AsciiStr {
+        self
+    }
+}
+
+impl AsMut<AsciiStr> for CompactAsciiString {
+    #[inline]
+    fn as_mut(&mut self) -> &mut AsciiStr {
+        self
+    }
+}
+
+impl AsRef<[AsciiChar]> for CompactAsciiString {
+    #[inline]
+    fn as_ref(&self) -> &[AsciiChar] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for CompactAsciiString {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl AsRef<str> for CompactAsciiString {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<AsciiStr> for CompactAsciiString {
+    #[inline]
+    fn borrow(&self) -> &AsciiStr {
+        self
+    }
+}
+
+impl BorrowMut<AsciiStr> for CompactAsciiString {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut AsciiStr {
+        self
+    }
+}
+
+impl<'a> From<&'a AsciiStr> for CompactAsciiString {
+    /// Stores the string inline if it fits.
+    fn from(string: &'a AsciiStr) -> Self {
+        if string.len() <= INLINE_CAPACITY {
+            let mut compact = CompactAsciiString::new();
+            compact.push_str(string);
+            compact
+        } else {
+            CompactAsciiString::from_vec(string.as_slice().to_vec())
+        }
+    }
+}
+
+impl From<AsciiChar> for CompactAsciiString {
+    #[inline]
+    fn from(ch: AsciiChar) -> Self {
+        let mut compact = CompactAsciiString::new();
+        compact.push(ch);
+        compact
+    }
+}
+
+impl From<AsciiString> for CompactAsciiString {
+    /// Takes over the allocation of `string`, without copying it.
+    ///
+    /// Use [`shrink_to_fit()`](CompactAsciiString::shrink_to_fit) afterwards to move a short
+    /// string inline and free the allocation.
+    #[inline]
+    fn from(string: AsciiString) -> Self {
+        CompactAsciiString::from_vec(string.into())
+    }
+}
+
+impl From<CompactAsciiString> for AsciiString {
+    #[inline]
+    fn from(string: CompactAsciiString) -> Self {
+        string.into_ascii_string()
+    }
+}
+
+impl<'a> From<&'a CompactAsciiString> for AsciiString {
+    #[inline]
+    fn from(string: &'a CompactAsciiString) -> Self {
+        AsciiString::from(string.as_ascii_str())
+    }
+}
+
+impl IntoIterator for CompactAsciiString {
+    type Item = AsciiChar;
+    type IntoIter = alloc::vec::IntoIter<AsciiChar>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Vec::from(self.into_ascii_string()).into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CompactAsciiString, INLINE_CAPACITY};
+    use alloc::vec::Vec;
+    use core::mem;
+    use {AsciiChar, AsciiStr, AsciiString};
+
+    #[test]
+    fn size() {
+        assert_eq!(
+            mem::size_of::<CompactAsciiString>(),
+            mem::size_of::<AsciiString>()
+        );
+        assert_eq!(INLINE_CAPACITY + 1, 3 * mem::size_of::<usize>());
+    }
+
+    #[test]
+    fn spill_and_shrink() {
+        let mut s = CompactAsciiString::new();
+        for i in 0..INLINE_CAPACITY {
+            s.push(AsciiChar::A);
+            assert!(s.is_inline());
+            assert_eq!(s.len(), i + 1);
+        }
+        s.push(AsciiChar::B);
+        assert!(!s.is_inline());
+        assert_eq!(s.len(), INLINE_CAPACITY + 1);
+        assert_eq!(s.last(), Some(AsciiChar::B));
+        assert_eq!(s.pop(), Some(AsciiChar::B));
+        assert!(!s.is_inline());
+        s.shrink_to_fit();
+        assert!(s.is_inline());
+        assert_eq!(s.as_slice(), &[AsciiChar::A; INLINE_CAPACITY][..]);
+
+        let long = AsciiStr::from_ascii(&[b'x'; 100][..]).unwrap();
+        s.push_str(long);
+        assert_eq!(s.len(), INLINE_CAPACITY + 100);
+        assert!(s.ends_with(long));
+        s.truncate(3);
+        s.shrink_to_fit();
+        assert!(s.is_inline());
+        assert_eq!(s, "AAA");
+    }
+
+    #[test]
+    fn edit() {
+        let mut s = CompactAsciiString::from_ascii("ace").unwrap();
+        s.insert(1, AsciiChar::b);
+        s.insert(3, AsciiChar::d);
+        s.insert_str(5, AsciiStr::from_ascii("fg").unwrap());
+        assert_eq!(s, "abcdefg");
+        assert_eq!(s.remove(0), AsciiChar::a);
+        assert_eq!(s.remove(5), AsciiChar::g);
+        assert_eq!(s, "bcdef");
+        s.insert_str(
+            2,
+            AsciiStr::from_ascii("0123456789012345678901234").unwrap(),
+        );
+        assert!(!s.is_inline());
+        assert_eq!(s, "bc0123456789012345678901234def");
+        assert_eq!(s.remove(2), AsciiChar::_0);
+        s.make_ascii_uppercase();
+        assert_eq!(s, "BC123456789012345678901234DEF");
+        s.clear();
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn conversions() {
+        let string = AsciiString::from_ascii("a string that is too long to be inline").unwrap();
+        let ptr = string.as_ptr();
+        let compact = CompactAsciiString::from(string);
+        assert_eq!(compact.as_ptr(), ptr);
+        let clone = compact.clone();
+        let string = AsciiString::from(compact);
+        assert_eq!(string.as_ptr(), ptr);
+        assert_eq!(clone, string);
+
+        let short = CompactAsciiString::from(AsciiStr::from_ascii("short").unwrap());
+        assert!(short.is_inline());
+        assert_eq!(short.clone().into_ascii_string(), "short");
+        assert_eq!(
+            short.into_iter().collect::<Vec<_>>(),
+            [
+                AsciiChar::s,
+                AsciiChar::h,
+                AsciiChar::o,
+                AsciiChar::r,
+                AsciiChar::t
+            ]
+        );
+        let collected: CompactAsciiString = "abc".parse::<AsciiString>().unwrap().chars().collect();
+        assert_eq!(collected, "abc");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_matches_ascii_str() {
+        use std::collections::HashSet;
+
+        let set: HashSet<CompactAsciiString> = ["key", "a much longer key for the heap"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        assert!(set.contains(AsciiStr::from_ascii("key").unwrap()));
+        assert!(set.contains(AsciiStr::from_ascii("a much longer key for the heap").unwrap()));
+    }
+}
@@ -0,0 +1,439 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+use ascii_char::AsciiChar;
+use ascii_str::AsciiStr;
+#[cfg(feature = "alloc")]
+use ascii_string::AsciiString;
+use encoding::{alphabet, values, DecodeError, DecodeErrorKind, IGNORED, INVALID};
+
+/// A binary-to-text encoding that represents every four bytes as five base-85 digits,
+/// such as [Ascii85](https://en.wikipedia.org/wiki/Ascii85) and
+/// [Z85](https://rfc.zeromq.org/spec/32/).
+///
+/// A last group of one to three bytes is encoded as two to four characters.
+/// The Z85 specification only allows input whose length is a multiple of four,
+/// so other lengths produce output that other Z85 implementations might reject.
+///
+/// The available encodings are the constants in the [`encoding`](crate::encoding) module.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Base85 {
+    name: &'static str,
+    alphabet: &'static [AsciiChar; 85],
+    /// The value of every ASCII character, or [`INVALID`] or [`IGNORED`].
+    values: [u8; 128],
+    /// Whether a group of four zero bytes is encoded as `z`.
+    zero_shorthand: bool,
+    /// Whether the encoded data is enclosed in `<~` and `~>`.
+    framed: bool,
+}
+
+const ASCII85_ALPHABET: [AsciiChar; 85] = alphabet(
+    b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu",
+);
+const Z85_ALPHABET: [AsciiChar; 85] = alphabet(
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#",
+);
+
+/// Ascii85 as used by PostScript and PDF: enclosed in `<~` and `~>`,
+/// and with `z` for four zero bytes.
+///
+/// Decoding ignores whitespace, including whitespace before `<~` and after `~>`,
+/// and accepts input without the opening `<~`, as found in PDF streams,
+/// but requires the closing `~>`.
+pub const ASCII85: Base85 = Base85 {
+    name: "ASCII85",
+    alphabet: &ASCII85_ALPHABET,
+    values: values(&ASCII85_ALPHABET, &[], b" \t\n\x0b\x0c\r"),
+    zero_shorthand: true,
+    framed: true,
+};
+
+/// Ascii85 without the `<~` and `~>` delimiters, but with `z` for four zero bytes.
+///
+/// Decoding ignores whitespace.
+pub const ASCII85_NOFRAME: Base85 = Base85 {
+    name: "ASCII85_NOFRAME",
+    framed: false,
+    ..ASCII85
+};
+
+/// Z85, whose alphabet is safe to use in source code string literals and XML.
+pub const Z85: Base85 = Base85 {
+    name: "Z85",
+    alphabet: &Z85_ALPHABET,
+    values: values(&Z85_ALPHABET, &[], &[]),
+    zero_shorthand: false,
+    framed: false,
+};
+
+impl Base85 {
+    /// Returns the maximum length of the encoded form of `len` bytes,
+    /// which is reached when no group of four bytes is all zeroes.
+    ///
+    /// # Examples
+    /// ```
+    /// use ascii::encoding::{ASCII85, Z85};
+    /// assert_eq!(Z85.max_encoded_len(8), 10);
+    /// assert_eq!(ASCII85.max_encoded_len(9), 2 + 12 + 2);
+    /// ```
+    #[must_use]
+    pub const fn max_encoded_len(&self, len: usize) -> usize {
+        let rest = len % 4;
+        let rest_chars = if rest == 0 { 0 } else { rest + 1 };
+        let delimiters = if self.framed { 4 } else { 0 };
+        len / 4 * 5 + rest_chars + delimiters
+    }
+
+    /// Returns the maximum number of bytes that `len` encoded characters can decode to.
+    ///
+    /// A buffer of this length is always long enough for
+    /// [`decode_to_slice()`](#method.decode_to_slice).
+    /// With the `z` shorthand this is four times `len`.
+    #[must_use]
+    pub const fn max_decoded_len(&self, len: usize) -> usize {
+        if self.zero_shorthand {
+            len.saturating_mul(4)
+        } else {
+            len / 5 * 4 + len % 5
+        }
+    }
+
+    /// Passes the encoded form of `bytes` to `push` one character at a time.
+    fn encode_with<F: FnMut(AsciiChar)>(&self, bytes: &[u8], mut push: F) {
+        if self.framed {
+            push(AsciiChar::LessThan);
+            push(AsciiChar::Tilde);
+        }
+        for chunk in bytes.chunks(4) {
+            let mut group = [0; 4];
+            for (byte, &input) in group.iter_mut().zip(chunk) {
+                *byte = input;
+            }
+            let mut value = u32::from_be_bytes(group);
+            if value == 0 && chunk.len() == 4 && self.zero_shorthand {
+                push(AsciiChar::z);
+                continue;
+            }
+            let mut digits = [0; 5];
+            for digit in digits.iter_mut().rev() {
+                *digit = value % 85;
+                value /= 85;
+            }
+            for &digit in digits.iter().take(chunk.len() + 1) {
+                #[allow(clippy::indexing_slicing)] // digits are below 85
+                push(self.alphabet[digit as usize]);
+            }
+        }
+        if self.framed {
+            push(AsciiChar::Tilde);
+            push(AsciiChar::GreaterThan);
+        }
+    }
+
+    /// Encodes `bytes` into the start of `buf` and returns the written part of `buf`.
+    ///
+    /// # Panics
+    /// If `buf` is shorter than [`max_encoded_len(bytes.len())`](#method.max_encoded_len).
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// use ascii::encoding::Z85;
+    /// let mut buf = [AsciiChar::Null; 16];
+    /// let buf: &mut AsciiStr = (&mut buf[..]).into();
+    /// let bytes = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+    /// assert_eq!(Z85.encode_to_slice(&bytes, buf), "HelloWorld");
+    /// ```
+    pub fn encode_to_slice<'a>(&self, bytes: &[u8], buf: &'a mut AsciiStr) -> &'a mut AsciiStr {
+        assert!(
+            buf.len() >= self.max_encoded_len(bytes.len()),
+            "buffer of length {} is too short for {} encoded characters",
+            buf.len(),
+            self.max_encoded_len(bytes.len())
+        );
+        let mut written = 0;
+        let mut slots = buf.as_mut_slice().iter_mut();
+        self.encode_with(bytes, |ch| {
+            if let Some(slot) = slots.next() {
+                *slot = ch;
+                written += 1;
+            }
+        });
+        buf.as_mut_slice().split_at_mut(written).0.into()
+    }
+
+    /// Encodes `bytes` into a new `AsciiString`.
+    ///
+    /// # Examples
+    /// ```
+    /// use ascii::encoding::{ASCII85, ASCII85_NOFRAME};
+    /// assert_eq!(ASCII85.encode(b"Man is d"), "<~9jqo^BlbD-~>");
+    /// assert_eq!(ASCII85_NOFRAME.encode(&[0; 7]), "z!!!!");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn encode(&self, bytes: &[u8]) -> AsciiString {
+        let mut encoded = AsciiString::with_capacity(self.max_encoded_len(bytes.len()));
+        self.encode_with(bytes, |ch| encoded.push(ch));
+        encoded
+    }
+
+    /// Returns whether decoding skips `ch`.
+    fn ignores(&self, ch: AsciiChar) -> bool {
+        #[allow(clippy::indexing_slicing)] // `values` has an entry for every `AsciiChar`
+        let value = self.values[ch as usize];
+        value == IGNORED
+    }
+
+    /// Returns the index where the encoded data in a framed input starts,
+    /// after skipping leading whitespace and an opening `<~` if there is one.
+    fn frame_start(&self, chars: &[AsciiChar]) -> usize {
+        let first = chars
+            .iter()
+            .position(|&ch| !self.ignores(ch))
+            .unwrap_or(chars.len());
+        match chars.get(first..first + 2) {
+            Some(&[AsciiChar::LessThan, AsciiChar::Tilde]) => first + 2,
+            _ => 0,
+        }
+    }
+
+    /// Returns the index of the end of the encoded data that starts at `start` in a framed
+    /// input, after checking that it is followed by `~>` and nothing but whitespace.
+    fn frame_end(&self, chars: &[AsciiChar], start: usize) -> Result<usize, DecodeError> {
+        let missing = DecodeError::new(chars.len(), DecodeErrorKind::MissingDelimiter);
+        let end = chars
+            .iter()
+            .skip(start)
+            .position(|&ch| ch == AsciiChar::Tilde)
+            .ok_or(missing)?
+            + start;
+        match chars.get(end + 1) {
+            None => return Err(missing),
+            Some(&AsciiChar::GreaterThan) => {}
+            Some(&ch) => {
+                let kind = DecodeErrorKind::InvalidCharacter(ch);
+                return Err(DecodeError::new(end + 1, kind));
+            }
+        }
+        let mut trailing = chars.iter().enumerate().skip(end + 2);
+        match trailing.find(|&(_, &ch)| !self.ignores(ch)) {
+            None => Ok(end),
+            Some((position, &ch)) => {
+                let kind = DecodeErrorKind::InvalidCharacter(ch);
+                Err(DecodeError::new(position, kind))
+            }
+        }
+    }
+
+    /// Decodes `input` and passes every decoded group of bytes to `push`.
+    fn decode_with<F: FnMut(&[u8])>(
+        &self,
+        input: &AsciiStr,
+        mut push: F,
+    ) -> Result<(), DecodeError> {
+        let chars = input.as_slice();
+        let mut start = 0;
+        let mut end = chars.len();
+        if self.framed {
+            start = self.frame_start(chars);
+            end = self.frame_end(chars, start)?;
+        }
+
+        let mut acc = 0_u32;
+        let mut digits = 0;
+        let mut group_start = start;
+        for (position, &ch) in chars.iter().enumerate().take(end).skip(start) {
+            #[allow(clippy::indexing_slicing)] // `values` has an entry for every `AsciiChar`
+            let value = self.values[ch as usize];
+            if value == IGNORED {
+                continue;
+            }
+            if ch == AsciiChar::z && self.zero_shorthand && digits == 0 {
+                push(&[0; 4]);
+                continue;
+            }
+            if value == INVALID {
+                let kind = DecodeErrorKind::InvalidCharacter(ch);
+                return Err(DecodeError::new(position, kind));
+            }
+            if digits == 0 {
+                group_start = position;
+            }
+            acc = acc
+                .checked_mul(85)
+                .and_then(|acc| acc.checked_add(u32::from(value)))
+                .ok_or(DecodeError::new(group_start, DecodeErrorKind::Overflow))?;
+            digits += 1;
+            if digits == 5 {
+                push(&acc.to_be_bytes());
+                acc = 0;
+                digits = 0;
+            }
+        }
+
+        match digits {
+            0 => Ok(()),
+            1 => Err(DecodeError::new(end, DecodeErrorKind::InvalidLength)),
+            _ => {
+                // Pad with the highest digit so that truncating rounds to the encoded bytes.
+                for _ in digits..5 {
+                    acc = acc
+                        .checked_mul(85)
+                        .and_then(|acc| acc.checked_add(84))
+                        .ok_or(DecodeError::new(group_start, DecodeErrorKind::Overflow))?;
+                }
+                push(acc.to_be_bytes().split_at(digits - 1).0);
+                Ok(())
+            }
+        }
+    }
+
+    /// Decodes `input` into the start of `buf` and returns the written part of `buf`.
+    ///
+    /// # Errors
+    /// Returns an error with the offset of the first problem in `input`.
+    /// The content of `buf` is unspecified when an error is returned.
+    ///
+    /// # Panics
+    /// If `buf` is too short for the decoded bytes.
+    /// A buffer of length [`max_decoded_len(input.len())`](#method.max_decoded_len) is always
+    /// long enough.
+    pub fn decode_to_slice<'a>(
+        &self,
+        input: &AsciiStr,
+        buf: &'a mut [u8],
+    ) -> Result<&'a mut [u8], DecodeError> {
+        let mut written = 0;
+        self.decode_with(input, |bytes| {
+            assert!(
+                buf.len() - written >= bytes.len(),
+                "buffer of length {} is too short",
+                buf.len()
+            );
+            let rest = buf.split_at_mut(written).1;
+            rest.split_at_mut(bytes.len()).0.copy_from_slice(bytes);
+            written += bytes.len();
+        })?;
+        Ok(buf.split_at_mut(written).0)
+    }
+
+    /// Decodes `input` into a new `Vec`.
+    ///
+    /// # Errors
+    /// Returns an error with the offset of the first problem in `input`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// use ascii::encoding::{DecodeErrorKind, ASCII85};
+    /// let encoded = AsciiStr::from_ascii("<~9jqo^ Blb\nD-~>").unwrap();
+    /// assert_eq!(ASCII85.decode(encoded).unwrap(), b"Man is d");
+    ///
+    /// let unterminated = AsciiStr::from_ascii("<~9jqo^").unwrap();
+    /// let error = ASCII85.decode(unterminated).unwrap_err();
+    /// assert_eq!(error.kind(), DecodeErrorKind::MissingDelimiter);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decode(&self, input: &AsciiStr) -> Result<Vec<u8>, DecodeError> {
+        let mut decoded = Vec::with_capacity(input.len() / 5 * 4 + 3);
+        self.decode_with(input, |bytes| decoded.extend_from_slice(bytes))?;
+        Ok(decoded)
+    }
+}
+
+impl fmt::Debug for Base85 {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        fmtr.write_str(self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ASCII85, ASCII85_NOFRAME, Z85};
+    use encoding::test_util::{decode, decodes_to, error, round_trip};
+    use {AsciiChar, AsciiStr};
+
+    #[test]
+    fn ascii85() {
+        round_trip(ASCII85, b"", "<~~>");
+        round_trip(ASCII85, b"Man is d", "<~9jqo^BlbD-~>");
+        round_trip(ASCII85, b".", "<~/c~>");
+        round_trip(ASCII85, &[0xff; 4], "<~s8W-!~>");
+        round_trip(ASCII85, &[0; 3], "<~!!!!~>");
+        round_trip(ASCII85_NOFRAME, &[0; 8], "zz");
+        round_trip(ASCII85_NOFRAME, b"\0\0\0\0hi", "zBP@");
+        assert!(decodes_to(ASCII85, "9jqo^\r\nBlbD-~>", b"Man is d"));
+        assert!(decodes_to(ASCII85, "<~ z\tz /c ~>", b"\0\0\0\0\0\0\0\0."));
+        assert!(decodes_to(ASCII85, "\r\n <~/c~>\n", b"."));
+        assert!(decodes_to(ASCII85, "<~9jqo^BlbD-~> \r\n", b"Man is d"));
+    }
+
+    #[test]
+    fn z85() {
+        let hello = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+        round_trip(Z85, &hello, "HelloWorld");
+        round_trip(Z85, &[0; 4], "00000");
+        round_trip(Z85, &[0xff, 0xff], "%nJ");
+        assert!(decode(Z85, "Hello World").is_err());
+    }
+
+    #[test]
+    fn lengths() {
+        assert_eq!(ASCII85.max_encoded_len(0), 4);
+        assert_eq!(ASCII85.max_encoded_len(5), 11);
+        assert_eq!(Z85.max_encoded_len(8), 10);
+        assert_eq!(ASCII85.max_decoded_len(3), 12);
+        assert_eq!(Z85.max_decoded_len(10), 8);
+    }
+
+    #[test]
+    fn errors() {
+        use encoding::DecodeErrorKind::*;
+        assert_eq!(error(ASCII85, "<~9jqo^"), (7, MissingDelimiter));
+        assert_eq!(error(ASCII85, "<~9jqo^~"), (8, MissingDelimiter));
+        assert_eq!(
+            error(ASCII85, "<~9j~~>"),
+            (5, InvalidCharacter(AsciiChar::Tilde))
+        );
+        assert_eq!(
+            error(ASCII85, "<~9j~>\n!"),
+            (7, InvalidCharacter(AsciiChar::Exclamation))
+        );
+        assert_eq!(
+            error(ASCII85, "<~9jqvo~>"),
+            (5, InvalidCharacter(AsciiChar::v))
+        );
+        assert_eq!(
+            error(ASCII85, "<~9jzo~>"),
+            (4, InvalidCharacter(AsciiChar::z))
+        );
+        assert_eq!(error(ASCII85, "<~9jqo^B~>"), (8, InvalidLength));
+        assert_eq!(error(ASCII85_NOFRAME, "9jqo^s8W-\""), (5, Overflow));
+        assert_eq!(error(ASCII85_NOFRAME, "uuuu"), (0, Overflow));
+        assert_eq!(
+            error(Z85, "Hello~"),
+            (5, InvalidCharacter(AsciiChar::Tilde))
+        );
+        assert_eq!(error(Z85, "z"), (1, InvalidLength));
+    }
+
+    #[test]
+    #[should_panic = "buffer of length 7 is too short"]
+    fn zero_shorthand_overflows_buffer() {
+        let input = AsciiStr::from_ascii("zz").unwrap();
+        let _decoded = ASCII85_NOFRAME.decode_to_slice(input, &mut [0; 7]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn owned() {
+        let bytes = b"\0\0\0\0binary\xff";
+        let encoded = ASCII85.encode(bytes);
+        assert_eq!(encoded, "<~z@VK^gEd@Z~>");
+        assert_eq!(ASCII85.decode(&encoded).unwrap(), bytes);
+        assert_eq!(Z85.decode(&Z85.encode(bytes)).unwrap(), bytes);
+    }
+}
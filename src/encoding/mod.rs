@@ -0,0 +1,281 @@
+//! Binary-to-text encodings that produce ASCII.
+//!
+//! Encoders return an [`AsciiString`](crate::AsciiString) or write into an
+//! [`AsciiStr`](crate::AsciiStr), so their output doesn't need to be validated again,
+//! and decoders take an `AsciiStr` and report the offset of any error.
+//!
+//! [`Encoding`] covers the Base64, Base32 and Base16 variants,
+//! and [`Base85`] covers Ascii85 and Z85.
+//! Percent-encoding for URIs is done by [`percent_encode()`] with a [`PercentEncodeSet`],
+//! and decoded by [`AsciiStr::percent_decode()`](crate::AsciiStr::percent_decode).
+//!
+//! # Examples
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! # use ascii::AsciiStr;
+//! use ascii::encoding::{ASCII85, BASE32_CROCKFORD, BASE64, BASE64URL_NOPAD};
+//!
+//! assert_eq!(BASE64.encode(b"\xfb\xffhi"), "+/9oaQ==");
+//! assert_eq!(BASE64URL_NOPAD.encode(b"\xfb\xffhi"), "-_9oaQ");
+//!
+//! let id = AsciiStr::from_ascii("csqp-yrk1").unwrap();
+//! assert_eq!(BASE32_CROCKFORD.decode(id).unwrap(), b"fooba");
+//!
+//! assert_eq!(ASCII85.encode(b"\0\0\0\0hi"), "<~zBP@~>");
+//! # }
+//! ```
+
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use ascii_char::AsciiChar;
+
+mod ascii85;
+mod percent;
+mod rfc4648;
+
+pub use self::ascii85::{Base85, ASCII85, ASCII85_NOFRAME, Z85};
+#[cfg(feature = "alloc")]
+pub use self::percent::percent_encode;
+pub use self::percent::{percent_encode_iter, PercentDecode, PercentEncode, PercentEncodeSet};
+pub use self::rfc4648::{
+    Encoding, BASE16, BASE32, BASE32_CROCKFORD, BASE32_NOPAD, BASE64, BASE64URL, BASE64URL_NOPAD,
+    BASE64_NOPAD,
+};
+
+/// Marks characters that are not part of the alphabet in decoding tables.
+const INVALID: u8 = 0xff;
+/// Marks characters that are ignored when decoding in decoding tables.
+const IGNORED: u8 = 0xfe;
+
+/// Converts an alphabet to `AsciiChar`s in a const context.
+const fn alphabet<const N: usize>(chars: &[u8; N]) -> [AsciiChar; N] {
+    #![allow(clippy::indexing_slicing)] // .get() is not const yet
+    let mut alphabet = [AsciiChar::Null; N];
+    let mut i = 0;
+    while i < N {
+        alphabet[i] = AsciiChar::new(chars[i] as char);
+        i += 1;
+    }
+    alphabet
+}
+
+/// Uppercase hex digits, used by Base16 and percent-encoding.
+const BASE16_ALPHABET: [AsciiChar; 16] = alphabet(b"0123456789ABCDEF");
+
+/// Creates the decoding table for an alphabet.
+///
+/// `aliases` are pairs of an extra accepted character and the character it's treated as.
+const fn values(alphabet: &[AsciiChar], aliases: &[(u8, u8)], ignored: &[u8]) -> [u8; 128] {
+    #![allow(clippy::indexing_slicing)] // .get() is not const yet
+    #![allow(clippy::cast_possible_truncation)] // alphabets have at most 85 characters
+    let mut values = [INVALID; 128];
+    let mut i = 0;
+    while i < alphabet.len() {
+        values[alphabet[i] as usize] = i as u8;
+        i += 1;
+    }
+    i = 0;
+    while i < aliases.len() {
+        values[aliases[i].0 as usize] = values[aliases[i].1 as usize];
+        i += 1;
+    }
+    i = 0;
+    while i < ignored.len() {
+        values[ignored[i] as usize] = IGNORED;
+        i += 1;
+    }
+    values
+}
+
+/// Error returned when decoding fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodeError {
+    position: usize,
+    kind: DecodeErrorKind,
+}
+
+/// The reason decoding failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DecodeErrorKind {
+    /// A character that is not part of the encoding.
+    InvalidCharacter(AsciiChar),
+    /// The input ends in the middle of a byte.
+    InvalidLength,
+    /// Padding is missing, too long or followed by something else.
+    InvalidPadding,
+    /// The bits of the last character that are not part of a byte are not zero,
+    /// which means the input was not produced by an encoder.
+    TrailingBits,
+    /// A group of characters represents a number that doesn't fit in four bytes.
+    Overflow,
+    /// The closing delimiter (such as `~>` for Ascii85) is missing.
+    MissingDelimiter,
+}
+
+impl DecodeError {
+    pub(crate) const fn new(position: usize, kind: DecodeErrorKind) -> Self {
+        DecodeError { position, kind }
+    }
+
+    /// Returns the index into the input where the error was detected.
+    ///
+    /// For [`InvalidCharacter`](DecodeErrorKind::InvalidCharacter) and
+    /// [`TrailingBits`](DecodeErrorKind::TrailingBits) this is the position of the offending
+    /// character, for [`Overflow`](DecodeErrorKind::Overflow) it is the start of the group,
+    /// and for the other kinds it is where the encoded data or padding should have ended.
+    #[inline]
+    #[must_use]
+    pub const fn position(self) -> usize {
+        self.position
+    }
+
+    /// Returns what went wrong.
+    #[inline]
+    #[must_use]
+    pub const fn kind(self) -> DecodeErrorKind {
+        self.kind
+    }
+
+    /// Returns a description for this error, like `std::error::Error::description`.
+    #[cfg(not(feature = "std"))]
+    #[inline]
+    #[must_use]
+    pub const fn description(&self) -> &'static str {
+        self.kind.description()
+    }
+}
+
+impl DecodeErrorKind {
+    const fn description(self) -> &'static str {
+        match self {
+            DecodeErrorKind::InvalidCharacter(_) => "invalid character",
+            DecodeErrorKind::InvalidLength => "invalid length",
+            DecodeErrorKind::InvalidPadding => "invalid padding",
+            DecodeErrorKind::TrailingBits => "non-zero trailing bits",
+            DecodeErrorKind::Overflow => "group overflows four bytes",
+            DecodeErrorKind::MissingDelimiter => "missing closing delimiter",
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            DecodeErrorKind::InvalidCharacter(ch) => {
+                write!(
+                    fmtr,
+                    "invalid character {:?} at offset {}",
+                    ch, self.position
+                )
+            }
+            kind => write!(fmtr, "{} at offset {}", kind.description(), self.position),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DecodeError {
+    #[inline]
+    fn description(&self) -> &'static str {
+        self.kind.description()
+    }
+}
+
+/// Helpers for testing the buffer-based API that all encodings have.
+#[cfg(test)]
+mod test_util {
+    use super::{Base85, DecodeError, DecodeErrorKind, Encoding};
+    use {AsciiArrayString, AsciiChar, AsciiStr};
+
+    pub(super) trait Codec: Copy {
+        fn encode_to_slice<'a>(self, bytes: &[u8], buf: &'a mut AsciiStr) -> &'a mut AsciiStr;
+        fn decode_to_slice<'a>(
+            self,
+            input: &AsciiStr,
+            buf: &'a mut [u8],
+        ) -> Result<&'a mut [u8], DecodeError>;
+    }
+
+    impl Codec for Encoding {
+        fn encode_to_slice<'a>(self, bytes: &[u8], buf: &'a mut AsciiStr) -> &'a mut AsciiStr {
+            Encoding::encode_to_slice(&self, bytes, buf)
+        }
+        fn decode_to_slice<'a>(
+            self,
+            input: &AsciiStr,
+            buf: &'a mut [u8],
+        ) -> Result<&'a mut [u8], DecodeError> {
+            Encoding::decode_to_slice(&self, input, buf)
+        }
+    }
+
+    impl Codec for Base85 {
+        fn encode_to_slice<'a>(self, bytes: &[u8], buf: &'a mut AsciiStr) -> &'a mut AsciiStr {
+            Base85::encode_to_slice(&self, bytes, buf)
+        }
+        fn decode_to_slice<'a>(
+            self,
+            input: &AsciiStr,
+            buf: &'a mut [u8],
+        ) -> Result<&'a mut [u8], DecodeError> {
+            Base85::decode_to_slice(&self, input, buf)
+        }
+    }
+
+    pub(super) fn encode<C: Codec>(codec: C, bytes: &[u8]) -> AsciiArrayString<32> {
+        let mut buf = [AsciiChar::Null; 32];
+        let encoded = codec.encode_to_slice(bytes, (&mut buf[..]).into());
+        AsciiArrayString::try_from_ascii_str(encoded).unwrap()
+    }
+
+    pub(super) fn decode<C: Codec>(codec: C, input: &str) -> Result<(), DecodeError> {
+        let input = AsciiStr::from_ascii(input).unwrap();
+        codec.decode_to_slice(input, &mut [0; 32]).map(|_| ())
+    }
+
+    /// Checks if `input` decodes to `expected`.
+    pub(super) fn decodes_to<C: Codec>(codec: C, input: &str, expected: &[u8]) -> bool {
+        let input = AsciiStr::from_ascii(input).unwrap();
+        *codec.decode_to_slice(input, &mut [0; 32]).unwrap() == *expected
+    }
+
+    /// Checks that `bytes` encodes to `encoded` and back.
+    pub(super) fn round_trip<C: Codec>(codec: C, bytes: &[u8], encoded: &str) {
+        assert_eq!(encode(codec, bytes), encoded);
+        assert!(decodes_to(codec, encoded, bytes));
+    }
+
+    pub(super) fn error<C: Codec>(codec: C, input: &str) -> (usize, DecodeErrorKind) {
+        let error = decode(codec, input).unwrap_err();
+        (error.position(), error.kind())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::test_util::decode;
+    use super::{ASCII85, ASCII85_NOFRAME, BASE32_NOPAD, BASE64, Z85};
+
+    #[test]
+    fn display() {
+        let error = decode(BASE64, "Zm9v!").unwrap_err();
+        assert_eq!(error.to_string(), "invalid character '!' at offset 4");
+        let error = decode(BASE64, "Zm8").unwrap_err();
+        assert_eq!(error.to_string(), "invalid padding at offset 3");
+        let error = decode(ASCII85_NOFRAME, "s8W-\"").unwrap_err();
+        assert_eq!(error.to_string(), "group overflows four bytes at offset 0");
+        let error = decode(ASCII85, "<~").unwrap_err();
+        assert_eq!(error.to_string(), "missing closing delimiter at offset 2");
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(
+            format!("{:?}", (BASE64, BASE32_NOPAD, ASCII85, Z85)),
+            "(BASE64, BASE32_NOPAD, ASCII85, Z85)"
+        );
+    }
+}
@@ -0,0 +1,443 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::slice;
+
+use ascii_char::AsciiChar;
+use ascii_set::AsciiSet;
+use ascii_str::AsciiStr;
+#[cfg(feature = "alloc")]
+use ascii_string::AsciiString;
+use encoding::BASE16_ALPHABET;
+
+/// The characters that never need to be escaped in a URI:
+/// letters, digits, `-`, `.`, `_` and `~`.
+const UNRESERVED: AsciiSet = AsciiSet::ALPHANUMERIC.union(AsciiSet::from_chars(&[
+    AsciiChar::Minus,
+    AsciiChar::Dot,
+    AsciiChar::UnderScore,
+    AsciiChar::Tilde,
+]));
+/// The characters that delimit subcomponents of a URI component: `!$&'()*+,;=`.
+const SUB_DELIMS: AsciiSet = AsciiSet::from_chars(&[
+    AsciiChar::Exclamation,
+    AsciiChar::Dollar,
+    AsciiChar::Ampersand,
+    AsciiChar::Apostrophe,
+    AsciiChar::ParenOpen,
+    AsciiChar::ParenClose,
+    AsciiChar::Asterisk,
+    AsciiChar::Plus,
+    AsciiChar::Comma,
+    AsciiChar::Semicolon,
+    AsciiChar::Equal,
+]);
+/// The characters allowed in a path segment, query or fragment, except `%`.
+const PCHAR: AsciiSet = UNRESERVED
+    .union(SUB_DELIMS)
+    .with(AsciiChar::Colon)
+    .with(AsciiChar::At);
+
+/// Which bytes [`percent_encode()`] escapes.
+///
+/// Bytes that are not ASCII, and `%` itself, are always escaped,
+/// so that decoding always gives back the original bytes.
+///
+/// The presets follow the grammar of [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986)
+/// and of `application/x-www-form-urlencoded`, and can be adjusted with
+/// [`AsciiSet`] operations on [`escaped()`](#method.escaped) followed by
+/// [`new()`](#method.new).
+///
+/// # Examples
+/// ```
+/// # use ascii::AsciiChar;
+/// use ascii::encoding::{percent_encode_iter, PercentEncodeSet};
+/// const SEGMENT: PercentEncodeSet =
+///     PercentEncodeSet::new(PercentEncodeSet::PATH.escaped().with(AsciiChar::Slash));
+/// let encoded = percent_encode_iter(b"a/b c", SEGMENT);
+/// assert_eq!(encoded.to_string(), "a%2Fb%20c");
+/// let encoded = percent_encode_iter(b"a/b c", PercentEncodeSet::PATH);
+/// assert_eq!(encoded.to_string(), "a/b%20c");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PercentEncodeSet {
+    escaped: AsciiSet,
+    space_as_plus: bool,
+}
+
+impl PercentEncodeSet {
+    /// Escapes everything except the unreserved characters: letters, digits, `-`, `.`, `_`
+    /// and `~`.
+    ///
+    /// This is safe for any part of a URI.
+    pub const COMPONENT: PercentEncodeSet = PercentEncodeSet::keeping(UNRESERVED);
+    /// Keeps the characters that are allowed in a path, including `/`.
+    pub const PATH: PercentEncodeSet = PercentEncodeSet::keeping(PCHAR.with(AsciiChar::Slash));
+    /// Keeps the characters that are allowed in a query, except `&`, `=` and `+`,
+    /// so that it can be used for the names and values of query parameters.
+    pub const QUERY: PercentEncodeSet = PercentEncodeSet::keeping(
+        PCHAR
+            .with(AsciiChar::Slash)
+            .with(AsciiChar::Question)
+            .without(AsciiChar::Ampersand)
+            .without(AsciiChar::Equal)
+            .without(AsciiChar::Plus),
+    );
+    /// Keeps the characters that are allowed in the user information before the host,
+    /// including the `:` that separates user and password.
+    pub const USERINFO: PercentEncodeSet =
+        PercentEncodeSet::keeping(UNRESERVED.union(SUB_DELIMS).with(AsciiChar::Colon));
+    /// Encodes names and values of `application/x-www-form-urlencoded` data:
+    /// keeps letters, digits, `*`, `-`, `.` and `_`, and encodes space as `+`.
+    ///
+    /// Decode with [`PercentDecode::plus_as_space()`].
+    pub const FORM: PercentEncodeSet = PercentEncodeSet {
+        space_as_plus: true,
+        ..PercentEncodeSet::keeping(AsciiSet::ALPHANUMERIC.union(AsciiSet::from_chars(&[
+            AsciiChar::Asterisk,
+            AsciiChar::Minus,
+            AsciiChar::Dot,
+            AsciiChar::UnderScore,
+        ])))
+    };
+
+    /// Creates a set that escapes the characters in `escaped` and `%`.
+    #[inline]
+    #[must_use]
+    pub const fn new(escaped: AsciiSet) -> Self {
+        PercentEncodeSet {
+            escaped: escaped.with(AsciiChar::Percent),
+            space_as_plus: false,
+        }
+    }
+
+    const fn keeping(kept: AsciiSet) -> Self {
+        PercentEncodeSet::new(kept.complement())
+    }
+
+    /// Returns a copy of the set that encodes space as `+`, and escapes `+`.
+    #[inline]
+    #[must_use]
+    pub const fn with_space_as_plus(self) -> Self {
+        PercentEncodeSet {
+            escaped: self.escaped.with(AsciiChar::Plus),
+            space_as_plus: true,
+        }
+    }
+
+    /// Returns the ASCII characters that are escaped.
+    #[inline]
+    #[must_use]
+    pub const fn escaped(self) -> AsciiSet {
+        self.escaped
+    }
+
+    /// Checks if space is encoded as `+`.
+    #[inline]
+    #[must_use]
+    pub const fn space_as_plus(self) -> bool {
+        self.space_as_plus
+    }
+
+    /// Checks if `byte` is left as it is.
+    fn keeps(self, byte: u8) -> bool {
+        match AsciiChar::from_ascii(byte) {
+            Ok(ch) => !self.escaped.contains(ch),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Creates `%XX` for every byte.
+const fn percent_escapes() -> [[AsciiChar; 3]; 256] {
+    #![allow(clippy::indexing_slicing)] // .get() is not const yet
+    let mut escapes = [[AsciiChar::Percent; 3]; 256];
+    let mut i = 0;
+    while i < 256 {
+        escapes[i][1] = BASE16_ALPHABET[i >> 4];
+        escapes[i][2] = BASE16_ALPHABET[i & 0xf];
+        i += 1;
+    }
+    escapes
+}
+
+static PERCENT_ESCAPES: [[AsciiChar; 3]; 256] = percent_escapes();
+
+/// Percent-encodes `bytes` into a new `AsciiString`,
+/// escaping non-ASCII bytes and the characters in `set`.
+///
+/// # Examples
+/// ```
+/// use ascii::encoding::{percent_encode, PercentEncodeSet};
+/// let encoded = percent_encode("dé jà/vu?".as_bytes(), PercentEncodeSet::QUERY);
+/// assert_eq!(encoded, "d%C3%A9%20j%C3%A0/vu?");
+/// let encoded = percent_encode(b"1 + 1 = 2", PercentEncodeSet::FORM);
+/// assert_eq!(encoded, "1+%2B+1+%3D+2");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn percent_encode(bytes: &[u8], set: PercentEncodeSet) -> AsciiString {
+    let mut encoded = AsciiString::with_capacity(bytes.len());
+    for chunk in percent_encode_iter(bytes, set) {
+        encoded.push_str(chunk);
+    }
+    encoded
+}
+
+/// Returns a lazy iterator over the percent-encoded form of `bytes`,
+/// which yields runs of unescaped characters and single escapes.
+///
+/// The iterator also implements `Display`.
+///
+/// # Examples
+/// ```
+/// # use ascii::AsciiStr;
+/// use ascii::encoding::{percent_encode_iter, PercentEncodeSet};
+/// let mut encoded = percent_encode_iter(b"a b/c", PercentEncodeSet::COMPONENT);
+/// assert_eq!(encoded.next().map(AsciiStr::as_str), Some("a"));
+/// assert_eq!(encoded.next().map(AsciiStr::as_str), Some("%20"));
+/// assert_eq!(encoded.to_string(), "b%2Fc");
+/// ```
+pub fn percent_encode_iter(bytes: &[u8], set: PercentEncodeSet) -> PercentEncode<'_> {
+    PercentEncode { bytes, set }
+}
+
+/// Iterator over the percent-encoded form of a byte slice.
+///
+/// This struct is created by [`percent_encode_iter()`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PercentEncode<'a> {
+    bytes: &'a [u8],
+    set: PercentEncodeSet,
+}
+
+impl<'a> Iterator for PercentEncode<'a> {
+    type Item = &'a AsciiStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&first, rest) = self.bytes.split_first()?;
+        if first == b' ' && self.set.space_as_plus {
+            self.bytes = rest;
+            return Some(slice::from_ref(&AsciiChar::Plus).into());
+        } else if !self.set.keeps(first) {
+            self.bytes = rest;
+            #[allow(clippy::indexing_slicing)] // there is an escape for every byte
+            return Some(PERCENT_ESCAPES[usize::from(first)][..].into());
+        }
+        let run = self
+            .bytes
+            .iter()
+            .position(|&byte| !self.set.keeps(byte) || byte == b' ' && self.set.space_as_plus)
+            .unwrap_or(self.bytes.len());
+        let (kept, rest) = self.bytes.split_at(run);
+        self.bytes = rest;
+        // SAFETY: `keeps()` only returns true for ASCII bytes.
+        Some(unsafe { AsciiStr::from_ascii_unchecked(kept) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bytes.len();
+        (usize::from(len != 0), Some(len))
+    }
+}
+
+impl<'a> FusedIterator for PercentEncode<'a> {}
+
+impl<'a> fmt::Display for PercentEncode<'a> {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        for chunk in self.clone() {
+            fmtr.write_str(chunk.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the bytes of a percent-encoded string.
+///
+/// `%` followed by two hex digits is decoded, and every other character is passed through
+/// as it is, including a `%` that doesn't start a valid escape.
+///
+/// This struct is created by [`AsciiStr::percent_decode_iter()`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PercentDecode<'a> {
+    chars: &'a [AsciiChar],
+    plus_as_space: bool,
+}
+
+impl<'a> PercentDecode<'a> {
+    pub(crate) fn new(encoded: &'a AsciiStr) -> Self {
+        PercentDecode {
+            chars: encoded.as_slice(),
+            plus_as_space: false,
+        }
+    }
+
+    /// Also decodes `+` as space, as used by `application/x-www-form-urlencoded`
+    /// and [`PercentEncodeSet::FORM`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// let encoded = AsciiStr::from_ascii("1+%2B+1").unwrap();
+    /// assert!(encoded.percent_decode_iter().plus_as_space().eq(b"1 + 1".iter().copied()));
+    /// ```
+    pub fn plus_as_space(self) -> Self {
+        PercentDecode {
+            plus_as_space: true,
+            ..self
+        }
+    }
+
+    /// Checks if decoding changes anything.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn is_identity(&self) -> bool {
+        !self
+            .chars
+            .iter()
+            .any(|&ch| ch == AsciiChar::Percent || ch == AsciiChar::Plus && self.plus_as_space)
+    }
+}
+
+impl<'a> Iterator for PercentDecode<'a> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let (&first, rest) = self.chars.split_first()?;
+        if let [AsciiChar::Percent, high, low, ref after @ ..] = *self.chars {
+            if let (Some(high), Some(low)) = (high.to_hex_digit(), low.to_hex_digit()) {
+                self.chars = after;
+                return Some(high << 4 | low);
+            }
+        }
+        self.chars = rest;
+        if first == AsciiChar::Plus && self.plus_as_space {
+            Some(b' ')
+        } else {
+            Some(first.as_byte())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.chars.len();
+        (len / 3, Some(len))
+    }
+}
+
+impl<'a> FusedIterator for PercentDecode<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{percent_encode_iter, PercentDecode, PercentEncodeSet};
+    use {AsciiChar, AsciiSet, AsciiStr};
+
+    fn encodes(bytes: &[u8], set: PercentEncodeSet, expected: &str) -> bool {
+        let chunks = percent_encode_iter(bytes, set).flat_map(AsciiStr::chars);
+        chunks.map(AsciiChar::as_char).eq(expected.chars())
+    }
+
+    fn decodes(encoded: &str, expected: &[u8]) -> bool {
+        let encoded = AsciiStr::from_ascii(encoded).unwrap();
+        PercentDecode::new(encoded).eq(expected.iter().copied())
+    }
+
+    #[test]
+    fn presets() {
+        let all: &[u8] = b" !\"#$%&'()*+,/:;<=>?@[\\]^`{|}~";
+        assert!(encodes(
+            all,
+            PercentEncodeSet::COMPONENT,
+            "%20%21%22%23%24%25%26%27%28%29%2A%2B%2C%2F%3A%3B%3C%3D%3E%3F%40%5B%5C%5D%5E%60%7B%7C%7D~"
+        ));
+        assert!(encodes(
+            all,
+            PercentEncodeSet::PATH,
+            "%20!%22%23$%25&'()*+,/:;%3C=%3E%3F@%5B%5C%5D%5E%60%7B%7C%7D~"
+        ));
+        assert!(encodes(
+            all,
+            PercentEncodeSet::QUERY,
+            "%20!%22%23$%25%26'()*%2B,/:;%3C%3D%3E?@%5B%5C%5D%5E%60%7B%7C%7D~"
+        ));
+        assert!(encodes(
+            all,
+            PercentEncodeSet::USERINFO,
+            "%20!%22%23$%25&'()*+,%2F:;%3C=%3E%3F%40%5B%5C%5D%5E%60%7B%7C%7D~"
+        ));
+        assert!(encodes(
+            all,
+            PercentEncodeSet::FORM,
+            "+%21%22%23%24%25%26%27%28%29*%2B%2C%2F%3A%3B%3C%3D%3E%3F%40%5B%5C%5D%5E%60%7B%7C%7D%7E"
+        ));
+    }
+
+    #[test]
+    fn encode() {
+        assert!(encodes(b"", PercentEncodeSet::COMPONENT, ""));
+        assert!(encodes(
+            b"\0\x7f\x80\xff",
+            PercentEncodeSet::PATH,
+            "%00%7F%80%FF"
+        ));
+        let set = PercentEncodeSet::new(AsciiSet::EMPTY);
+        assert!(encodes(b"50% off", set, "50%25 off"));
+        let set = set.with_space_as_plus();
+        assert!(set.space_as_plus());
+        assert!(encodes(b"1 + 1", set, "1+%2B+1"));
+
+        let mut chunks = percent_encode_iter(b"ab  cd", PercentEncodeSet::FORM);
+        assert_eq!(chunks.size_hint(), (1, Some(6)));
+        assert_eq!(chunks.next().map(AsciiStr::as_str), Some("ab"));
+        assert_eq!(chunks.next().map(AsciiStr::as_str), Some("+"));
+        assert_eq!(chunks.next().map(AsciiStr::as_str), Some("+"));
+        assert_eq!(chunks.next().map(AsciiStr::as_str), Some("cd"));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn decode() {
+        assert!(decodes("", b""));
+        assert!(decodes("a%20b%2fc%C3%A9", b"a b/c\xc3\xa9"));
+        assert!(decodes("100%", b"100%"));
+        assert!(decodes("%%41%4", b"%A%4"));
+        assert!(decodes("%zz+", b"%zz+"));
+        let encoded = AsciiStr::from_ascii("a+b%2B").unwrap();
+        assert!(PercentDecode::new(encoded)
+            .plus_as_space()
+            .eq(b"a b+".iter().copied()));
+    }
+
+    #[test]
+    fn round_trip() {
+        let bytes: &[u8] = &[0, 1, b' ', b'%', b'+', b'/', b'~', 0x7f, 0x80, 0xfe, 0xff];
+        let sets = [
+            PercentEncodeSet::COMPONENT,
+            PercentEncodeSet::PATH,
+            PercentEncodeSet::QUERY,
+            PercentEncodeSet::USERINFO,
+            PercentEncodeSet::new(AsciiSet::EMPTY),
+        ];
+        let mut buf = [AsciiChar::Null; 64];
+        for &set in &sets {
+            let mut len = 0;
+            for (slot, ch) in buf
+                .iter_mut()
+                .zip(percent_encode_iter(bytes, set).flat_map(AsciiStr::chars))
+            {
+                *slot = ch;
+                len += 1;
+            }
+            let encoded: &AsciiStr = buf[..len].into();
+            assert!(PercentDecode::new(encoded).eq(bytes.iter().copied()));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn display() {
+        use alloc::string::ToString;
+        let encoded = percent_encode_iter(b"a&b=c d", PercentEncodeSet::QUERY);
+        assert_eq!(encoded.to_string(), "a%26b%3Dc%20d");
+    }
+}
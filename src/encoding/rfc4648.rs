@@ -0,0 +1,511 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+use ascii_char::AsciiChar;
+use ascii_str::AsciiStr;
+#[cfg(feature = "alloc")]
+use ascii_string::AsciiString;
+use encoding::{alphabet, values, DecodeError, DecodeErrorKind, BASE16_ALPHABET, IGNORED, INVALID};
+
+/// A binary-to-text encoding where every character represents the same number of bits,
+/// such as Base64, Base32 and Base16 from [RFC 4648](https://www.rfc-editor.org/rfc/rfc4648).
+///
+/// The available encodings are the constants in the [`encoding`](crate::encoding) module.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Encoding {
+    name: &'static str,
+    alphabet: &'static [AsciiChar],
+    /// The value of every ASCII character, or [`INVALID`] or [`IGNORED`].
+    values: [u8; 128],
+    bits_per_char: u32,
+    padding: bool,
+}
+
+const BASE64_ALPHABET: [AsciiChar; 64] =
+    alphabet(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/");
+const BASE64URL_ALPHABET: [AsciiChar; 64] =
+    alphabet(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_");
+const BASE32_ALPHABET: [AsciiChar; 32] = alphabet(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567");
+const CROCKFORD_ALPHABET: [AsciiChar; 32] = alphabet(b"0123456789ABCDEFGHJKMNPQRSTVWXYZ");
+
+const LOWERCASE_HEX: &[(u8, u8)] = &[
+    (b'a', b'A'),
+    (b'b', b'B'),
+    (b'c', b'C'),
+    (b'd', b'D'),
+    (b'e', b'E'),
+    (b'f', b'F'),
+];
+const CROCKFORD_ALIASES: &[(u8, u8)] = &[
+    (b'a', b'A'),
+    (b'b', b'B'),
+    (b'c', b'C'),
+    (b'd', b'D'),
+    (b'e', b'E'),
+    (b'f', b'F'),
+    (b'g', b'G'),
+    (b'h', b'H'),
+    (b'j', b'J'),
+    (b'k', b'K'),
+    (b'm', b'M'),
+    (b'n', b'N'),
+    (b'p', b'P'),
+    (b'q', b'Q'),
+    (b'r', b'R'),
+    (b's', b'S'),
+    (b't', b'T'),
+    (b'v', b'V'),
+    (b'w', b'W'),
+    (b'x', b'X'),
+    (b'y', b'Y'),
+    (b'z', b'Z'),
+    (b'O', b'0'),
+    (b'o', b'0'),
+    (b'I', b'1'),
+    (b'i', b'1'),
+    (b'L', b'1'),
+    (b'l', b'1'),
+];
+
+/// Base64 with the standard alphabet (`+` and `/`) and `=` padding.
+pub const BASE64: Encoding = Encoding {
+    name: "BASE64",
+    alphabet: &BASE64_ALPHABET,
+    values: values(&BASE64_ALPHABET, &[], &[]),
+    bits_per_char: 6,
+    padding: true,
+};
+
+/// Base64 with the standard alphabet (`+` and `/`) and without padding.
+pub const BASE64_NOPAD: Encoding = Encoding {
+    name: "BASE64_NOPAD",
+    padding: false,
+    ..BASE64
+};
+
+/// Base64 with the URL and filename safe alphabet (`-` and `_`) and `=` padding.
+pub const BASE64URL: Encoding = Encoding {
+    name: "BASE64URL",
+    alphabet: &BASE64URL_ALPHABET,
+    values: values(&BASE64URL_ALPHABET, &[], &[]),
+    bits_per_char: 6,
+    padding: true,
+};
+
+/// Base64 with the URL and filename safe alphabet (`-` and `_`) and without padding.
+pub const BASE64URL_NOPAD: Encoding = Encoding {
+    name: "BASE64URL_NOPAD",
+    padding: false,
+    ..BASE64URL
+};
+
+/// Base32 with the RFC 4648 alphabet (`A` to `Z` then `2` to `7`) and `=` padding.
+pub const BASE32: Encoding = Encoding {
+    name: "BASE32",
+    alphabet: &BASE32_ALPHABET,
+    values: values(&BASE32_ALPHABET, &[], &[]),
+    bits_per_char: 5,
+    padding: true,
+};
+
+/// Base32 with the RFC 4648 alphabet (`A` to `Z` then `2` to `7`) and without padding.
+pub const BASE32_NOPAD: Encoding = Encoding {
+    name: "BASE32_NOPAD",
+    padding: false,
+    ..BASE32
+};
+
+/// [Crockford's Base32](https://www.crockford.com/base32.html), without padding or check symbol.
+///
+/// Encoding produces uppercase characters.
+/// Decoding is case-insensitive, reads `O` as `0` and `I` and `L` as `1`,
+/// and ignores hyphens.
+pub const BASE32_CROCKFORD: Encoding = Encoding {
+    name: "BASE32_CROCKFORD",
+    alphabet: &CROCKFORD_ALPHABET,
+    values: values(&CROCKFORD_ALPHABET, CROCKFORD_ALIASES, b"-"),
+    bits_per_char: 5,
+    padding: false,
+};
+
+/// Base16, which is uppercase hex.
+///
+/// Decoding also accepts lowercase letters.
+pub const BASE16: Encoding = Encoding {
+    name: "BASE16",
+    alphabet: &BASE16_ALPHABET,
+    values: values(&BASE16_ALPHABET, LOWERCASE_HEX, &[]),
+    bits_per_char: 4,
+    padding: false,
+};
+
+impl Encoding {
+    /// Returns the number of characters in a block, which is what padding rounds up to.
+    const fn block_chars(&self) -> usize {
+        match self.bits_per_char {
+            6 => 4,
+            5 => 8,
+            _ => 2,
+        }
+    }
+
+    /// Returns the number of bytes a block of characters encodes.
+    #[allow(clippy::cast_possible_truncation)] // at most 8 characters of 6 bits
+    const fn block_bytes(&self) -> usize {
+        self.block_chars() * self.bits_per_char as usize / 8
+    }
+
+    /// Returns the length of the encoded form of `len` bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// use ascii::encoding::{BASE64, BASE64_NOPAD};
+    /// assert_eq!(BASE64.encoded_len(4), 8);
+    /// assert_eq!(BASE64_NOPAD.encoded_len(4), 6);
+    /// ```
+    #[must_use]
+    pub const fn encoded_len(&self, len: usize) -> usize {
+        let rest = len % self.block_bytes();
+        let rest_chars = if rest == 0 {
+            0
+        } else if self.padding {
+            self.block_chars()
+        } else {
+            (rest * 8 - 1) / self.bits_per_char as usize + 1
+        };
+        len / self.block_bytes() * self.block_chars() + rest_chars
+    }
+
+    /// Returns the maximum number of bytes that `len` encoded characters can decode to.
+    ///
+    /// A buffer of this length is always long enough for
+    /// [`decode_to_slice()`](#method.decode_to_slice).
+    #[must_use]
+    pub const fn max_decoded_len(&self, len: usize) -> usize {
+        let bits = self.bits_per_char as usize;
+        len / 8 * bits + len % 8 * bits / 8
+    }
+
+    #[allow(clippy::indexing_slicing)] // `value` is masked to the length of the alphabet
+    fn symbol(&self, value: u32) -> AsciiChar {
+        self.alphabet[(value & ((1 << self.bits_per_char) - 1)) as usize]
+    }
+
+    /// Encodes `bytes` into the start of `buf` and returns the written part of `buf`.
+    ///
+    /// # Panics
+    /// If `buf` is shorter than [`encoded_len(bytes.len())`](#method.encoded_len).
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::{AsciiChar, AsciiStr};
+    /// use ascii::encoding::BASE32;
+    /// let mut buf = [AsciiChar::Null; 16];
+    /// let buf: &mut AsciiStr = (&mut buf[..]).into();
+    /// assert_eq!(BASE32.encode_to_slice(b"hi", buf), "NBUQ====");
+    /// ```
+    pub fn encode_to_slice<'a>(&self, bytes: &[u8], buf: &'a mut AsciiStr) -> &'a mut AsciiStr {
+        assert!(
+            buf.len() >= self.encoded_len(bytes.len()),
+            "buffer of length {} is too short for {} encoded characters",
+            buf.len(),
+            self.encoded_len(bytes.len())
+        );
+        let out = buf
+            .as_mut_slice()
+            .split_at_mut(self.encoded_len(bytes.len()))
+            .0;
+        let mut slots = out.iter_mut();
+        let mut acc = 0_u32;
+        let mut bits = 0;
+        for &byte in bytes {
+            acc = acc << 8 | u32::from(byte);
+            bits += 8;
+            while bits >= self.bits_per_char {
+                bits -= self.bits_per_char;
+                if let Some(slot) = slots.next() {
+                    *slot = self.symbol(acc >> bits);
+                }
+            }
+            acc &= (1 << bits) - 1;
+        }
+        if bits > 0 {
+            if let Some(slot) = slots.next() {
+                *slot = self.symbol(acc << (self.bits_per_char - bits));
+            }
+        }
+        for slot in slots {
+            *slot = AsciiChar::Equal;
+        }
+        out.into()
+    }
+
+    /// Encodes `bytes` into a new `AsciiString`.
+    ///
+    /// # Examples
+    /// ```
+    /// use ascii::encoding::{BASE16, BASE64};
+    /// assert_eq!(BASE64.encode(b"foobar"), "Zm9vYmFy");
+    /// assert_eq!(BASE16.encode(b"\x01\xab"), "01AB");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn encode(&self, bytes: &[u8]) -> AsciiString {
+        let mut encoded = vec![AsciiChar::Null; self.encoded_len(bytes.len())];
+        let _written = self.encode_to_slice(bytes, (&mut encoded[..]).into());
+        AsciiString::from(encoded)
+    }
+
+    /// Decodes `input` into the start of `buf` and returns the written part of `buf`.
+    ///
+    /// # Errors
+    /// Returns an error with the offset of the first problem in `input`.
+    /// The content of `buf` is unspecified when an error is returned.
+    ///
+    /// # Panics
+    /// If `buf` is too short for the decoded bytes.
+    /// A buffer of length [`max_decoded_len(input.len())`](#method.max_decoded_len) is always
+    /// long enough.
+    pub fn decode_to_slice<'a>(
+        &self,
+        input: &AsciiStr,
+        buf: &'a mut [u8],
+    ) -> Result<&'a mut [u8], DecodeError> {
+        let chars = input.as_slice();
+        let data_end = match chars.iter().position(|&ch| ch == AsciiChar::Equal) {
+            Some(padding_start) if self.padding => padding_start,
+            _ => chars.len(),
+        };
+        let (data, padding) = chars.split_at(data_end);
+        if padding.iter().any(|&ch| ch != AsciiChar::Equal) {
+            return Err(DecodeError::new(data_end, DecodeErrorKind::InvalidPadding));
+        }
+
+        let mut written = 0;
+        let mut acc = 0_u32;
+        let mut bits = 0;
+        let mut symbols = 0;
+        let mut last_symbol = 0;
+        for (position, &ch) in data.iter().enumerate() {
+            #[allow(clippy::indexing_slicing)] // `values` has an entry for every `AsciiChar`
+            let value = self.values[ch as usize];
+            if value == IGNORED {
+                continue;
+            }
+            if value == INVALID {
+                let kind = DecodeErrorKind::InvalidCharacter(ch);
+                return Err(DecodeError::new(position, kind));
+            }
+            acc = acc << self.bits_per_char | u32::from(value);
+            bits += self.bits_per_char;
+            symbols += 1;
+            last_symbol = position;
+            if bits >= 8 {
+                bits -= 8;
+                #[allow(clippy::cast_possible_truncation)] // the byte is masked off below
+                let byte = (acc >> bits) as u8;
+                assert!(
+                    written < buf.len(),
+                    "buffer of length {} is too short",
+                    buf.len()
+                );
+                if let Some(slot) = buf.get_mut(written) {
+                    *slot = byte;
+                }
+                written += 1;
+                acc &= (1 << bits) - 1;
+            }
+        }
+
+        if bits >= self.bits_per_char {
+            return Err(DecodeError::new(data_end, DecodeErrorKind::InvalidLength));
+        }
+        if acc != 0 {
+            return Err(DecodeError::new(last_symbol, DecodeErrorKind::TrailingBits));
+        }
+        if self.padding {
+            let partial = symbols % self.block_chars();
+            let expected = if partial == 0 {
+                0
+            } else {
+                self.block_chars() - partial
+            };
+            if padding.len() != expected {
+                return Err(DecodeError::new(data_end, DecodeErrorKind::InvalidPadding));
+            }
+        }
+        Ok(buf.split_at_mut(written).0)
+    }
+
+    /// Decodes `input` into a new `Vec`.
+    ///
+    /// # Errors
+    /// Returns an error with the offset of the first problem in `input`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ascii::AsciiStr;
+    /// use ascii::encoding::{DecodeErrorKind, BASE64};
+    /// let encoded = AsciiStr::from_ascii("Zm9v YmFy").unwrap();
+    /// let error = BASE64.decode(encoded).unwrap_err();
+    /// assert_eq!(error.position(), 4);
+    /// assert!(matches!(error.kind(), DecodeErrorKind::InvalidCharacter(_)));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decode(&self, input: &AsciiStr) -> Result<Vec<u8>, DecodeError> {
+        let mut decoded = vec![0; self.max_decoded_len(input.len())];
+        let len = self.decode_to_slice(input, &mut decoded)?.len();
+        decoded.truncate(len);
+        Ok(decoded)
+    }
+}
+
+impl fmt::Debug for Encoding {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        fmtr.write_str(self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Encoding, BASE16, BASE32, BASE32_CROCKFORD, BASE32_NOPAD, BASE64, BASE64URL,
+        BASE64URL_NOPAD, BASE64_NOPAD,
+    };
+    use encoding::test_util::{decode, decodes_to, encode, error, round_trip};
+    use {AsciiChar, AsciiStr};
+
+    const FOOBAR: [&[u8]; 7] = [b"", b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar"];
+
+    fn foobar(encoding: Encoding, expected: [&str; 7]) {
+        for (&bytes, &encoded) in FOOBAR.iter().zip(&expected) {
+            round_trip(encoding, bytes, encoded);
+            assert_eq!(encoding.encoded_len(bytes.len()), encoded.len());
+            assert!(encoding.max_decoded_len(encoded.len()) >= bytes.len());
+        }
+    }
+
+    #[test]
+    fn rfc4648_vectors() {
+        let base64 = [
+            "", "Zg==", "Zm8=", "Zm9v", "Zm9vYg==", "Zm9vYmE=", "Zm9vYmFy",
+        ];
+        foobar(BASE64, base64);
+        foobar(BASE64URL, base64);
+        foobar(
+            BASE64_NOPAD,
+            ["", "Zg", "Zm8", "Zm9v", "Zm9vYg", "Zm9vYmE", "Zm9vYmFy"],
+        );
+        foobar(
+            BASE64URL_NOPAD,
+            ["", "Zg", "Zm8", "Zm9v", "Zm9vYg", "Zm9vYmE", "Zm9vYmFy"],
+        );
+        foobar(
+            BASE32,
+            [
+                "",
+                "MY======",
+                "MZXQ====",
+                "MZXW6===",
+                "MZXW6YQ=",
+                "MZXW6YTB",
+                "MZXW6YTBOI======",
+            ],
+        );
+        foobar(
+            BASE32_NOPAD,
+            [
+                "",
+                "MY",
+                "MZXQ",
+                "MZXW6",
+                "MZXW6YQ",
+                "MZXW6YTB",
+                "MZXW6YTBOI",
+            ],
+        );
+        foobar(
+            BASE32_CROCKFORD,
+            [
+                "",
+                "CR",
+                "CSQG",
+                "CSQPY",
+                "CSQPYRG",
+                "CSQPYRK1",
+                "CSQPYRK1E8",
+            ],
+        );
+        foobar(
+            BASE16,
+            [
+                "",
+                "66",
+                "666F",
+                "666F6F",
+                "666F6F62",
+                "666F6F6261",
+                "666F6F626172",
+            ],
+        );
+    }
+
+    #[test]
+    fn alphabets() {
+        assert_eq!(encode(BASE64, b"\xfb\xff\xbf"), "+/+/");
+        assert_eq!(encode(BASE64URL, b"\xfb\xff\xbf"), "-_-_");
+        assert!(decode(BASE64, "-_-_").is_err());
+        assert!(decode(BASE64URL, "+/+/").is_err());
+
+        assert!(decodes_to(BASE16, "c0fFEe", b"\xc0\xff\xee"));
+        assert!(decodes_to(BASE32_CROCKFORD, "csqp-Yrk1-e8", b"foobar"));
+        assert!(decodes_to(BASE32_CROCKFORD, "oiL0", &[0x00, 0x42]));
+        assert!(decode(BASE32, "mzxw6===").is_err());
+    }
+
+    #[test]
+    fn errors() {
+        use encoding::DecodeErrorKind::*;
+        assert_eq!(
+            error(BASE64, "Zm9v!mFy"),
+            (4, InvalidCharacter(AsciiChar::Exclamation))
+        );
+        assert_eq!(
+            error(BASE64_NOPAD, "Zm8="),
+            (3, InvalidCharacter(AsciiChar::Equal))
+        );
+        assert_eq!(error(BASE64_NOPAD, "Zm9vY"), (5, InvalidLength));
+        assert_eq!(error(BASE64, "Zm9vY==="), (5, InvalidLength));
+        assert_eq!(error(BASE64, "Zm8"), (3, InvalidPadding));
+        assert_eq!(error(BASE64, "Zg="), (2, InvalidPadding));
+        assert_eq!(error(BASE64, "Zm8=Zm8="), (3, InvalidPadding));
+        assert_eq!(error(BASE64, "Zh=="), (1, TrailingBits));
+        assert_eq!(error(BASE32, "MZXW6YQ"), (7, InvalidPadding));
+        assert_eq!(error(BASE32, "MZX====="), (3, InvalidLength));
+        assert_eq!(error(BASE16, "6"), (1, InvalidLength));
+        assert_eq!(
+            error(BASE32_CROCKFORD, "CSQU"),
+            (3, InvalidCharacter(AsciiChar::U))
+        );
+    }
+
+    #[test]
+    #[should_panic = "buffer of length 1 is too short"]
+    fn decode_short_buffer() {
+        let input = AsciiStr::from_ascii("Zm9v").unwrap();
+        let _decoded = BASE64.decode_to_slice(input, &mut [0; 1]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn owned() {
+        let encoded = BASE32_CROCKFORD.encode(&[0xff; 5]);
+        assert_eq!(encoded, "ZZZZZZZZ");
+        assert_eq!(BASE32_CROCKFORD.decode(&encoded).unwrap(), [0xff; 5]);
+        assert_eq!(
+            BASE64.decode(AsciiStr::from_ascii("").unwrap()).unwrap(),
+            b""
+        );
+    }
+}
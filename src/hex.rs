@@ -0,0 +1,249 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use ascii_char::AsciiChar;
+use ascii_str::AsciiStr;
+
+/// Returns the hex digit for the low four bits of `value`.
+pub(crate) fn hex_digit(value: u8, uppercase: bool) -> AsciiChar {
+    // `value & 0xf` is always a valid digit.
+    let digit = AsciiChar::from_hex_digit(value & 0xf).unwrap_or(AsciiChar::Null);
+    if uppercase {
+        digit.to_ascii_uppercase()
+    } else {
+        digit
+    }
+}
+
+/// Writes `bytes` as lowercase hex digits into the start of `buf`,
+/// and returns the written part of `buf`.
+///
+/// This is the variant of [`AsciiString::from_hex_lower()`](crate::AsciiString::from_hex_lower)
+/// that doesn't allocate.
+///
+/// # Panics
+/// If `buf` is shorter than twice the length of `bytes`.
+///
+/// # Examples
+/// ```
+/// # use ascii::{encode_hex_lower_to_slice, AsciiChar};
+/// let mut buf = [AsciiChar::Null; 8];
+/// assert_eq!(encode_hex_lower_to_slice(&[0xc0, 0xff, 0xee], &mut buf), "c0ffee");
+/// ```
+pub fn encode_hex_lower_to_slice<'a>(bytes: &[u8], buf: &'a mut [AsciiChar]) -> &'a mut AsciiStr {
+    encode_to_slice(bytes, buf, false)
+}
+
+/// Writes `bytes` as uppercase hex digits into the start of `buf`,
+/// and returns the written part of `buf`.
+///
+/// This is the variant of [`AsciiString::from_hex_upper()`](crate::AsciiString::from_hex_upper)
+/// that doesn't allocate.
+///
+/// # Panics
+/// If `buf` is shorter than twice the length of `bytes`.
+///
+/// # Examples
+/// ```
+/// # use ascii::{encode_hex_upper_to_slice, AsciiChar};
+/// let mut buf = [AsciiChar::Null; 6];
+/// assert_eq!(encode_hex_upper_to_slice(&[0xc0, 0xff, 0xee], &mut buf), "C0FFEE");
+/// ```
+pub fn encode_hex_upper_to_slice<'a>(bytes: &[u8], buf: &'a mut [AsciiChar]) -> &'a mut AsciiStr {
+    encode_to_slice(bytes, buf, true)
+}
+
+fn encode_to_slice<'a>(
+    bytes: &[u8],
+    buf: &'a mut [AsciiChar],
+    uppercase: bool,
+) -> &'a mut AsciiStr {
+    assert!(
+        buf.len() >= bytes.len() * 2,
+        "buffer of length {} is too short for {} hex digits",
+        buf.len(),
+        bytes.len() * 2
+    );
+    let out = buf.split_at_mut(bytes.len() * 2).0;
+    for (pair, &byte) in out.chunks_exact_mut(2).zip(bytes) {
+        if let [high, low] = pair {
+            *high = hex_digit(byte >> 4, uppercase);
+            *low = hex_digit(byte, uppercase);
+        }
+    }
+    out.into()
+}
+
+/// Decodes pairs of hex digits from `hex` into the start of `buf`,
+/// and returns the written part of `buf`.
+///
+/// # Errors
+/// If `hex` contains a character that isn't a hex digit or has an odd length.
+///
+/// # Panics
+/// If `buf` is shorter than half the length of `hex`.
+pub(crate) fn decode_to_slice<'a>(
+    hex: &AsciiStr,
+    buf: &'a mut [u8],
+) -> Result<&'a mut [u8], HexError> {
+    assert!(
+        buf.len() >= hex.len() / 2,
+        "buffer of length {} is too short for {} decoded bytes",
+        buf.len(),
+        hex.len() / 2
+    );
+    let out = buf.split_at_mut(hex.len() / 2).0;
+    let mut pairs = hex.as_slice().chunks_exact(2);
+    for (index, (pair, byte)) in pairs.by_ref().zip(out.iter_mut()).enumerate() {
+        if let [high, low] = *pair {
+            let high = high
+                .to_hex_digit()
+                .ok_or(HexError::invalid(2 * index, high))?;
+            let low = low
+                .to_hex_digit()
+                .ok_or(HexError::invalid(2 * index + 1, low))?;
+            *byte = high << 4 | low;
+        }
+    }
+    match *pairs.remainder() {
+        [last] if !last.is_ascii_hexdigit() => Err(HexError::invalid(hex.len() - 1, last)),
+        [_] => Err(HexError {
+            position: hex.len(),
+            character: None,
+        }),
+        _ => Ok(out),
+    }
+}
+
+/// Error returned when decoding hex fails.
+///
+/// Both uppercase and lowercase digits are accepted, but nothing else, not even whitespace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HexError {
+    position: usize,
+    character: Option<AsciiChar>,
+}
+
+impl HexError {
+    fn invalid(position: usize, character: AsciiChar) -> Self {
+        HexError {
+            position,
+            character: Some(character),
+        }
+    }
+
+    /// Returns the index of the character that is not a hex digit,
+    /// or the length of the input if it has an odd number of digits.
+    #[inline]
+    #[must_use]
+    pub const fn position(self) -> usize {
+        self.position
+    }
+
+    /// Returns the character that is not a hex digit,
+    /// or `None` if the input has an odd number of digits.
+    #[inline]
+    #[must_use]
+    pub const fn invalid_char(self) -> Option<AsciiChar> {
+        self.character
+    }
+
+    /// Returns a description for this error, like `std::error::Error::description`.
+    #[cfg(not(feature = "std"))]
+    #[inline]
+    #[must_use]
+    pub const fn description(&self) -> &'static str {
+        match self.character {
+            Some(_) => "invalid hex digit",
+            None => "odd number of hex digits",
+        }
+    }
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        match self.character {
+            Some(ch) => write!(
+                fmtr,
+                "invalid hex digit {:?} at index {}",
+                ch, self.position
+            ),
+            None => write!(fmtr, "odd number of hex digits ({})", self.position),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for HexError {
+    #[inline]
+    fn description(&self) -> &'static str {
+        match self.character {
+            Some(_) => "invalid hex digit",
+            None => "odd number of hex digits",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_to_slice, encode_hex_lower_to_slice, encode_hex_upper_to_slice, HexError};
+    use {AsciiChar, AsciiStr};
+
+    fn decode<'a>(hex: &str, buf: &'a mut [u8]) -> Result<&'a mut [u8], HexError> {
+        decode_to_slice(AsciiStr::from_ascii(hex).unwrap(), buf)
+    }
+
+    #[test]
+    fn encode() {
+        let mut buf = [AsciiChar::Null; 10];
+        assert_eq!(encode_hex_lower_to_slice(&[], &mut buf), "");
+        assert_eq!(
+            encode_hex_lower_to_slice(&[0, 0x9a, 0xff], &mut buf),
+            "009aff"
+        );
+        assert_eq!(
+            encode_hex_upper_to_slice(&[0, 0x9a, 0xff], &mut buf),
+            "009AFF"
+        );
+        assert_eq!(buf[6], AsciiChar::Null);
+    }
+
+    #[test]
+    #[should_panic = "buffer of length 3 is too short for 4 hex digits"]
+    fn encode_short_buffer() {
+        let _hex = encode_hex_lower_to_slice(&[1, 2], &mut [AsciiChar::Null; 3]);
+    }
+
+    #[test]
+    fn decode_valid() {
+        let mut buf = [0xaa; 4];
+        assert_eq!(decode("", &mut buf), Ok(&mut [][..]));
+        assert_eq!(decode("0aF9", &mut buf), Ok(&mut [0x0a, 0xf9][..]));
+        assert_eq!(buf, [0x0a, 0xf9, 0xaa, 0xaa]);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        let mut buf = [0; 4];
+        let error = decode("0a 9", &mut buf).unwrap_err();
+        assert_eq!(error.position(), 2);
+        assert_eq!(error.invalid_char(), Some(AsciiChar::Space));
+        let error = decode("0a9g", &mut buf).unwrap_err();
+        assert_eq!(error.position(), 3);
+        assert_eq!(error.invalid_char(), Some(AsciiChar::g));
+        let error = decode("0a9", &mut buf).unwrap_err();
+        assert_eq!(error.position(), 3);
+        assert_eq!(error.invalid_char(), None);
+        assert_eq!(decode("0ax", &mut buf).unwrap_err().position(), 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn display() {
+        let error = decode("0a9g", &mut [0; 2]).unwrap_err();
+        assert_eq!(error.to_string(), "invalid hex digit 'g' at index 3");
+        let error = decode("abc", &mut [0; 2]).unwrap_err();
+        assert_eq!(error.to_string(), "odd number of hex digits (3)");
+    }
+}
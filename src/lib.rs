@@ -66,17 +66,83 @@ extern crate serde;
 #[cfg(all(test, feature = "serde_test"))]
 extern crate serde_test;
 
+#[cfg(feature = "transliteration")]
+extern crate unicode_normalization;
+
+#[cfg(all(feature = "std", feature = "futures-io"))]
+extern crate futures_io;
+
+#[cfg(all(feature = "std", feature = "tokio"))]
+extern crate tokio;
+
+#[macro_use]
+mod macros;
+
+mod ascii_array_string;
 mod ascii_char;
+#[cfg(feature = "std")]
+mod ascii_cstr;
+#[cfg(feature = "std")]
+mod ascii_reader;
+mod ascii_set;
 mod ascii_str;
 #[cfg(feature = "alloc")]
 mod ascii_string;
+#[cfg(feature = "std")]
+mod ascii_writer;
+#[cfg(all(feature = "std", any(feature = "futures-io", feature = "tokio")))]
+#[macro_use]
+mod async_io;
+#[cfg(all(feature = "std", feature = "futures-io"))]
+pub mod async_futures;
+#[cfg(all(feature = "std", feature = "tokio"))]
+pub mod async_tokio;
+mod chunks;
+#[cfg(feature = "alloc")]
+mod compact_ascii_string;
+pub mod encoding;
 mod free_functions;
+mod hex;
+mod lossy;
+mod pattern;
+mod report;
 #[cfg(feature = "serde")]
 mod serialization;
+#[cfg(feature = "transliteration")]
+mod transliterate;
+mod uncased;
+mod validate;
 
+pub use ascii_array_string::{AsciiArrayString, CapacityError};
 pub use ascii_char::{AsciiChar, ToAsciiChar, ToAsciiCharError};
+#[cfg(feature = "std")]
+pub use ascii_cstr::{AsciiCStr, AsciiCString, InteriorNulError};
+#[cfg(feature = "std")]
+pub use ascii_reader::{AsciiLines, AsciiReader};
+pub use ascii_set::{AsciiSet, AsciiSetIter};
 pub use ascii_str::{AsAsciiStr, AsAsciiStrError, AsMutAsciiStr, AsciiStr};
 pub use ascii_str::{Chars, CharsMut, CharsRef};
+pub use ascii_str::{MatchIndices, Matches, RMatchIndices, RMatches};
+pub use ascii_str::{
+    RSplit, RSplitN, RSplitTerminator, Split, SplitInclusive, SplitN, SplitTerminator,
+    SplitWhitespace,
+};
 #[cfg(feature = "alloc")]
-pub use ascii_string::{AsciiString, FromAsciiError, IntoAsciiString};
+pub use ascii_string::{
+    AsciiString, FromAsciiError, IntoAsciiString, NonAsciiFormatError, SplitWhitespaceOwned,
+};
+#[cfg(feature = "std")]
+pub use ascii_writer::AsciiWriter;
+pub use chunks::{ascii_chunks, AsciiChunk, AsciiChunks};
+#[cfg(feature = "alloc")]
+pub use compact_ascii_string::CompactAsciiString;
 pub use free_functions::{caret_decode, caret_encode};
+pub use hex::{encode_hex_lower_to_slice, encode_hex_upper_to_slice, HexError};
+pub use lossy::LossyStrategy;
+pub use pattern::{AsciiPattern, DoubleEndedAsciiPattern};
+pub use report::{non_ascii_ranges, AsciiValidationReport, NonAsciiRanges};
+#[cfg(feature = "transliteration")]
+pub use transliterate::{Transliterate, TransliterationFallback};
+pub use uncased::UncasedAsciiStr;
+#[cfg(feature = "alloc")]
+pub use uncased::UncasedAsciiString;
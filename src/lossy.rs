@@ -0,0 +1,54 @@
+use ascii_char::AsciiChar;
+#[cfg(feature = "alloc")]
+use free_functions::caret_encode;
+#[cfg(feature = "alloc")]
+use hex::hex_digit;
+
+/// What lossy conversions such as
+/// [`AsciiString::from_ascii_lossy_with()`](crate::AsciiString::from_ascii_lossy_with)
+/// do with bytes that are not ASCII.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LossyStrategy {
+    /// Replace every non-ASCII byte with the given character.
+    Replace(AsciiChar),
+    /// Leave out non-ASCII bytes.
+    Drop,
+    /// Escape non-ASCII bytes the way `cat -v` does: as `M-` followed by the byte with the
+    /// high bit cleared, in [caret notation](https://en.wikipedia.org/wiki/Caret_notation)
+    /// if that is a control code.
+    ///
+    /// For example 0xC3 becomes `M-C` and 0x80 becomes `M-^@`.
+    CaretEscape,
+    /// Escape non-ASCII bytes as `\x` followed by two lowercase hex digits,
+    /// like [`u8::escape_ascii()`] does.
+    HexEscape,
+}
+
+impl LossyStrategy {
+    /// Calls `push` with each character that the non-ASCII `byte` is replaced with.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn replace<F: FnMut(AsciiChar)>(self, byte: u8, mut push: F) {
+        match self {
+            LossyStrategy::Replace(replacement) => push(replacement),
+            LossyStrategy::Drop => {}
+            LossyStrategy::CaretEscape => {
+                push(AsciiChar::M);
+                push(AsciiChar::Minus);
+                let low = byte & 0x7f;
+                if let Some(letter) = caret_encode(low) {
+                    push(AsciiChar::Caret);
+                    push(letter);
+                } else {
+                    // SAFETY: `low` has the high bit cleared.
+                    push(unsafe { AsciiChar::from_ascii_unchecked(low) });
+                }
+            }
+            LossyStrategy::HexEscape => {
+                push(AsciiChar::BackSlash);
+                push(AsciiChar::x);
+                push(hex_digit(byte >> 4, false));
+                push(hex_digit(byte, false));
+            }
+        }
+    }
+}
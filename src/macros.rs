@@ -0,0 +1,221 @@
+/// Creates a `&'static AsciiStr` from a string literal, checked at compile time.
+///
+/// The argument can be any constant expression of type `&'static str`.
+/// As the check happens during constant evaluation, the macro can be used in `const` and
+/// `static` items, and it works without `std`.
+///
+/// # Examples
+/// ```
+/// # #[macro_use] extern crate ascii;
+/// # use ascii::AsciiStr;
+/// # fn main() {
+/// const GREETING: &AsciiStr = ascii!("HELO example.com");
+/// static VERSION: &AsciiStr = ascii!("HTTP/1.1");
+/// let crlf = ascii!("\r\n");
+///
+/// assert_eq!(GREETING.len(), 16);
+/// assert_eq!(VERSION, "HTTP/1.1");
+/// assert_eq!(crlf.as_bytes(), b"\r\n");
+/// # }
+/// ```
+///
+/// A non-ASCII character fails to compile with an "index out of bounds" error,
+/// where the index is the byte offset of the first non-ASCII byte:
+/// ```compile_fail
+/// # #[macro_use] extern crate ascii;
+/// # use ascii::AsciiStr;
+/// # fn main() {
+/// // error: index out of bounds: the length is 0 but the index is 3
+/// const PRICE: &AsciiStr = ascii!("10 €");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! ascii {
+    ($s:expr) => {{
+        #[allow(clippy::indexing_slicing)]
+        const ASCII_STR: &'static $crate::AsciiStr = match $crate::AsciiStr::from_ascii_str($s) {
+            Ok(ascii_str) => ascii_str,
+            Err(error) => {
+                // Panicking with a message in `const` requires Rust 1.57,
+                // so indexing out of bounds is used to report the position instead.
+                let non_ascii_byte_at_index: [&'static $crate::AsciiStr; 0] = [];
+                non_ascii_byte_at_index[error.valid_up_to()]
+            }
+        };
+        ASCII_STR
+    }};
+}
+
+/// Creates an `AsciiChar` from a character literal, checked at compile time.
+///
+/// The argument can be any constant expression of type `char`.
+/// As the check happens during constant evaluation, the macro can be used in `const` and
+/// `static` items, and it works without `std`.
+///
+/// # Examples
+/// ```
+/// # #[macro_use] extern crate ascii;
+/// # use ascii::AsciiChar;
+/// # fn main() {
+/// const SEPARATOR: AsciiChar = ascii_char!(':');
+/// assert_eq!(SEPARATOR, AsciiChar::Colon);
+/// assert_eq!(ascii_char!('\n'), AsciiChar::LineFeed);
+/// # }
+/// ```
+///
+/// A non-ASCII character fails to compile with an "index out of bounds" error,
+/// where the index is the code point of the character:
+/// ```compile_fail
+/// # #[macro_use] extern crate ascii;
+/// # use ascii::AsciiChar;
+/// # fn main() {
+/// // error: index out of bounds: the length is 0 but the index is 233
+/// const E: AsciiChar = ascii_char!('é');
+/// # }
+/// ```
+#[macro_export]
+macro_rules! ascii_char {
+    ($c:expr) => {{
+        #[allow(clippy::indexing_slicing)]
+        const ASCII_CHAR: $crate::AsciiChar = match $crate::AsciiChar::try_new($c) {
+            Ok(ch) => ch,
+            Err(_) => {
+                // Panicking with a message in `const` requires Rust 1.57,
+                // so indexing out of bounds is used to report the code point instead.
+                let non_ascii_code_point: [$crate::AsciiChar; 0] = [];
+                non_ascii_code_point[$c as usize]
+            }
+        };
+        ASCII_CHAR
+    }};
+}
+
+/// Creates an `AsciiString` using interpolation of runtime expressions, like `format!()`.
+///
+/// # Errors
+/// Evaluates to `Err(NonAsciiFormatError)` if the formatted text contains a non-ASCII
+/// character. The error tells which character and at which byte offset.
+///
+/// # Panics
+/// Panics if a formatting trait implementation returns an error, like `format!()` does.
+///
+/// # Examples
+/// ```
+/// # #[macro_use] extern crate ascii;
+/// # fn main() {
+/// let (code, reason) = (250, "OK");
+/// let reply = format_ascii!("{} {}\r\n", code, reason).unwrap();
+/// assert_eq!(reply, "250 OK\r\n");
+///
+/// let error = format_ascii!("{} {}\r\n", 550, "Ungültig").unwrap_err();
+/// assert_eq!(error.offset(), 7);
+/// assert_eq!(error.character(), 'ü');
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! format_ascii {
+    ($($arg:tt)*) => {{
+        let mut string = $crate::AsciiString::new();
+        $crate::AsciiString::write_ascii_fmt(&mut string, format_args!($($arg)*)).map(|()| string)
+    }};
+}
+
+/// Appends formatted text to an `AsciiString`, like `write!()`.
+///
+/// The first argument can be a mutable `AsciiString` or a `&mut AsciiString`.
+/// The macro evaluates to the result of [`AsciiString::write_ascii_fmt()`], so the destination
+/// is left unchanged if the formatted text isn't ASCII, and the offset in the error is relative
+/// to the start of the text this invocation formatted.
+///
+/// # Errors
+/// Evaluates to `Err(NonAsciiFormatError)` if the formatted text contains a non-ASCII character.
+///
+/// # Examples
+/// ```
+/// # #[macro_use] extern crate ascii;
+/// # use ascii::{AsciiString, NonAsciiFormatError};
+/// # fn main() {
+/// fn header(dst: &mut AsciiString, name: &str, value: &str) -> Result<(), NonAsciiFormatError> {
+///     write_ascii!(dst, "{}: {}\r\n", name, value)
+/// }
+/// let mut request = AsciiString::new();
+/// write_ascii!(request, "GET {} HTTP/1.1\r\n", "/").unwrap();
+/// header(&mut request, "Host", "example.com").unwrap();
+///
+/// let error = header(&mut request, "X-Name", "Zoë").unwrap_err();
+/// assert_eq!(error.offset(), 10);
+/// assert_eq!(request, "GET / HTTP/1.1\r\nHost: example.com\r\n");
+/// # }
+/// ```
+///
+/// [`AsciiString::write_ascii_fmt()`]: struct.AsciiString.html#method.write_ascii_fmt
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! write_ascii {
+    ($dst:expr, $($arg:tt)*) => {
+        $dst.write_ascii_fmt(format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use {AsciiChar, AsciiStr};
+
+    const HELLO: &AsciiStr = ascii!("Hello");
+    static EMPTY: &AsciiStr = ascii!("");
+    const TILDE: AsciiChar = ascii_char!('~');
+    static NUL: AsciiChar = ascii_char!('\0');
+
+    #[test]
+    fn in_items() {
+        assert_eq!(HELLO, "Hello");
+        assert!(EMPTY.is_empty());
+        assert_eq!(TILDE, AsciiChar::Tilde);
+        assert_eq!(NUL, AsciiChar::Null);
+    }
+
+    #[test]
+    fn in_expressions() {
+        const NAME: &str = "Content-Length";
+        assert_eq!(ascii!(NAME).len(), 14);
+        assert_eq!(ascii!("\x7f").as_slice(), &[AsciiChar::DEL]);
+        assert_eq!(ascii_char!('\x7f'), AsciiChar::DEL);
+        assert_eq!(ascii!("a").first(), Some(ascii_char!('a')));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn format() {
+        use core::fmt;
+        use AsciiString;
+
+        let reply = format_ascii!("{:03} {:>4}|{:x}", 7, "ok", 255).unwrap();
+        assert_eq!(reply, "007   ok|ff");
+        assert_eq!(format_ascii!("").unwrap(), "");
+
+        let error = format_ascii!("{}{}", "ab", 'é').unwrap_err();
+        assert_eq!((error.offset(), error.character()), (2, 'é'));
+        let error = format_ascii!("{:?}", "\u{1f600}").unwrap_err();
+        assert_eq!((error.offset(), error.character()), (1, '\u{1f600}'));
+
+        struct Sloppy;
+        impl fmt::Display for Sloppy {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let _ = f.write_str("ab\u{e9}");
+                f.write_str("cd\u{fc}")
+            }
+        }
+        let error = format_ascii!("{}", Sloppy).unwrap_err();
+        assert_eq!((error.offset(), error.character()), (2, '\u{e9}'));
+
+        let mut line = AsciiString::from_ascii("PRIVMSG").unwrap();
+        let dst = &mut line;
+        write_ascii!(dst, " #{}", "rust").unwrap();
+        let error = write_ascii!(dst, " :{} {}", "hi", "naïve").unwrap_err();
+        assert_eq!((error.offset(), error.character()), (7, 'ï'));
+        assert_eq!(line, "PRIVMSG #rust");
+        write_ascii!(line, " :{}", "hi").unwrap();
+        assert_eq!(line, "PRIVMSG #rust :hi");
+    }
+}
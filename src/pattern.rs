@@ -0,0 +1,319 @@
+use ascii_char::AsciiChar;
+use ascii_set::AsciiSet;
+use ascii_str::AsciiStr;
+#[cfg(feature = "alloc")]
+use ascii_string::AsciiString;
+
+/// A pattern that can be searched for in an [`AsciiStr`].
+///
+/// This is the equivalent of `std::str::pattern::Pattern`, which is not stable yet.
+/// It is implemented for
+///
+/// * [`AsciiChar`], which matches that character,
+/// * `&AsciiStr` (and `&AsciiString`), which matches that substring,
+/// * `&[AsciiChar]` and `[AsciiChar; N]`, which match any of the characters in the slice,
+/// * [`AsciiSet`] (and `&AsciiSet`), which matches any of the characters in the set,
+/// * closures and functions of the form `FnMut(AsciiChar) -> bool`, such as the character
+///   classification methods [`AsciiChar::is_whitespace()`] or [`AsciiChar::is_alphanumeric()`],
+///   which match every character they return `true` for.
+///
+/// All positions are byte indices into the haystack, which can be used to slice it.
+///
+/// # Examples
+/// ```
+/// # use ascii::{AsciiChar, AsciiStr};
+/// let s = AsciiStr::from_ascii("key = value").unwrap();
+/// assert_eq!(s.find(AsciiChar::Equal), Some(4));
+/// assert_eq!(s.find(AsciiStr::from_ascii("val").unwrap()), Some(6));
+/// assert_eq!(s.find(&[AsciiChar::u, AsciiChar::y][..]), Some(2));
+/// assert_eq!(s.find(AsciiChar::is_whitespace), Some(3));
+/// assert_eq!(s.rfind(|ch: AsciiChar| ch.is_ascii_punctuation()), Some(4));
+/// ```
+pub trait AsciiPattern: Sized {
+    /// Returns the start and end index of the first match in `haystack`, if there is one.
+    fn find_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)>;
+
+    /// Returns the start and end index of the last match in `haystack`, if there is one.
+    fn rfind_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)>;
+
+    /// Returns the length of the match if `haystack` starts with this pattern.
+    ///
+    /// The default implementation searches the whole haystack with
+    /// [`find_in()`](#tymethod.find_in), so implementors should override it if they can
+    /// check for a prefix cheaper.
+    fn prefix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        match self.find_in(haystack) {
+            Some((0, end)) => Some(end),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of the match if `haystack` ends with this pattern.
+    ///
+    /// The default implementation searches the whole haystack with
+    /// [`rfind_in()`](#tymethod.rfind_in), so implementors should override it if they can
+    /// check for a suffix cheaper.
+    fn suffix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        match self.rfind_in(haystack) {
+            Some((start, end)) if end == haystack.len() => Some(end - start),
+            _ => None,
+        }
+    }
+}
+
+/// A marker for patterns where searching from the back finds the same matches as searching from
+/// the front.
+///
+/// This is true for all patterns that only ever match a single character, and makes the iterators
+/// returned by methods like [`AsciiStr::match_indices()`] double-ended.
+/// It is not true for substrings: searching for `"aa"` in `"aaa"` matches `0..2` from the front,
+/// but `1..3` from the back.
+pub trait DoubleEndedAsciiPattern: AsciiPattern {}
+
+impl AsciiPattern for AsciiChar {
+    #[inline]
+    fn find_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        let needle = *self;
+        haystack
+            .chars()
+            .position(|ch| ch == needle)
+            .map(|at| (at, at + 1))
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        let needle = *self;
+        haystack
+            .chars()
+            .rposition(|ch| ch == needle)
+            .map(|at| (at, at + 1))
+    }
+
+    #[inline]
+    fn prefix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        haystack.first().filter(|&ch| ch == *self).map(|_| 1)
+    }
+
+    #[inline]
+    fn suffix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        haystack.last().filter(|&ch| ch == *self).map(|_| 1)
+    }
+}
+impl DoubleEndedAsciiPattern for AsciiChar {}
+
+impl<'b> AsciiPattern for &'b [AsciiChar] {
+    fn find_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        let set = *self;
+        haystack
+            .chars()
+            .position(|ch| set.contains(&ch))
+            .map(|at| (at, at + 1))
+    }
+
+    fn rfind_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        let set = *self;
+        haystack
+            .chars()
+            .rposition(|ch| set.contains(&ch))
+            .map(|at| (at, at + 1))
+    }
+
+    fn prefix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        haystack.first().filter(|ch| self.contains(ch)).map(|_| 1)
+    }
+
+    fn suffix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        haystack.last().filter(|ch| self.contains(ch)).map(|_| 1)
+    }
+}
+impl<'b> DoubleEndedAsciiPattern for &'b [AsciiChar] {}
+
+impl<const N: usize> AsciiPattern for [AsciiChar; N] {
+    #[inline]
+    fn find_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        (&self[..]).find_in(haystack)
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        (&self[..]).rfind_in(haystack)
+    }
+
+    #[inline]
+    fn prefix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        (&self[..]).prefix_len_in(haystack)
+    }
+
+    #[inline]
+    fn suffix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        (&self[..]).suffix_len_in(haystack)
+    }
+}
+impl<const N: usize> DoubleEndedAsciiPattern for [AsciiChar; N] {}
+
+impl<'b, const N: usize> AsciiPattern for &'b [AsciiChar; N] {
+    #[inline]
+    fn find_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        (&self[..]).find_in(haystack)
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        (&self[..]).rfind_in(haystack)
+    }
+
+    #[inline]
+    fn prefix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        (&self[..]).prefix_len_in(haystack)
+    }
+
+    #[inline]
+    fn suffix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        (&self[..]).suffix_len_in(haystack)
+    }
+}
+impl<'b, const N: usize> DoubleEndedAsciiPattern for &'b [AsciiChar; N] {}
+
+impl AsciiPattern for AsciiSet {
+    #[inline]
+    fn find_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        let set = *self;
+        haystack
+            .chars()
+            .position(|ch| set.contains(ch))
+            .map(|at| (at, at + 1))
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        let set = *self;
+        haystack
+            .chars()
+            .rposition(|ch| set.contains(ch))
+            .map(|at| (at, at + 1))
+    }
+
+    #[inline]
+    fn prefix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        haystack.first().filter(|&ch| self.contains(ch)).map(|_| 1)
+    }
+
+    #[inline]
+    fn suffix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        haystack.last().filter(|&ch| self.contains(ch)).map(|_| 1)
+    }
+}
+impl DoubleEndedAsciiPattern for AsciiSet {}
+
+impl<'b> AsciiPattern for &'b AsciiSet {
+    #[inline]
+    fn find_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        let mut set = **self;
+        set.find_in(haystack)
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        let mut set = **self;
+        set.rfind_in(haystack)
+    }
+
+    #[inline]
+    fn prefix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        let mut set = **self;
+        set.prefix_len_in(haystack)
+    }
+
+    #[inline]
+    fn suffix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        let mut set = **self;
+        set.suffix_len_in(haystack)
+    }
+}
+impl<'b> DoubleEndedAsciiPattern for &'b AsciiSet {}
+
+impl<F> AsciiPattern for F
+where
+    F: FnMut(AsciiChar) -> bool,
+{
+    #[inline]
+    fn find_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        haystack.chars().position(self).map(|at| (at, at + 1))
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        haystack.chars().rposition(self).map(|at| (at, at + 1))
+    }
+
+    #[inline]
+    fn prefix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        haystack.first().filter(|&ch| self(ch)).map(|_| 1)
+    }
+
+    #[inline]
+    fn suffix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        haystack.last().filter(|&ch| self(ch)).map(|_| 1)
+    }
+}
+impl<F> DoubleEndedAsciiPattern for F where F: FnMut(AsciiChar) -> bool {}
+
+/// An empty substring matches at every position, including the end of the haystack.
+impl<'b> AsciiPattern for &'b AsciiStr {
+    fn find_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        let needle = self.as_bytes();
+        if needle.is_empty() {
+            return Some((0, 0));
+        }
+        haystack
+            .as_bytes()
+            .windows(needle.len())
+            .position(|window| window == needle)
+            .map(|at| (at, at + needle.len()))
+    }
+
+    fn rfind_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        let needle = self.as_bytes();
+        if needle.is_empty() {
+            return Some((haystack.len(), haystack.len()));
+        }
+        haystack
+            .as_bytes()
+            .windows(needle.len())
+            .rposition(|window| window == needle)
+            .map(|at| (at, at + needle.len()))
+    }
+
+    #[inline]
+    fn prefix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        haystack.strip_prefix_ascii_str(self).map(|_| self.len())
+    }
+
+    #[inline]
+    fn suffix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        haystack.strip_suffix_ascii_str(self).map(|_| self.len())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'b> AsciiPattern for &'b AsciiString {
+    #[inline]
+    fn find_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        (&***self).find_in(haystack)
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &AsciiStr) -> Option<(usize, usize)> {
+        (&***self).rfind_in(haystack)
+    }
+
+    #[inline]
+    fn prefix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        (&***self).prefix_len_in(haystack)
+    }
+
+    #[inline]
+    fn suffix_len_in(&mut self, haystack: &AsciiStr) -> Option<usize> {
+        (&***self).suffix_len_in(haystack)
+    }
+}
@@ -0,0 +1,249 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use ascii_str::AsAsciiStrError;
+use validate::ascii_prefix_len;
+
+/// Returns an iterator over the byte ranges of all non-ASCII sequences in `bytes`.
+///
+/// Each UTF-8 encoded character is reported as one range, so for a `str` every range covers
+/// exactly one non-ASCII `char`.
+/// Bytes that are not part of a valid UTF-8 sequence are reported as ranges of length one.
+///
+/// # Examples
+/// ```
+/// # use ascii::non_ascii_ranges;
+/// let ranges: Vec<_> = non_ascii_ranges("na\u{ef}ve \u{2192} caf\u{e9}").collect();
+/// assert_eq!(ranges, [2..4, 7..10, 14..16]);
+///
+/// let ranges: Vec<_> = non_ascii_ranges(b"ok\xff\xfe\xc3\xa9").collect();
+/// assert_eq!(ranges, [2..3, 3..4, 4..6]);
+/// ```
+pub fn non_ascii_ranges<B: AsRef<[u8]> + ?Sized>(bytes: &B) -> NonAsciiRanges<'_> {
+    NonAsciiRanges {
+        bytes: bytes.as_ref(),
+        offset: 0,
+    }
+}
+
+/// An iterator over the byte ranges of the non-ASCII sequences in a byte slice.
+///
+/// This struct is created by [`non_ascii_ranges()`].
+#[derive(Clone, Debug)]
+pub struct NonAsciiRanges<'a> {
+    bytes: &'a [u8],
+    /// The position in `bytes` to continue searching from.
+    offset: usize,
+}
+
+impl<'a> Iterator for NonAsciiRanges<'a> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        let rest = self.bytes.get(self.offset..)?;
+        let valid_up_to = ascii_prefix_len(rest);
+        if valid_up_to == rest.len() {
+            self.offset = self.bytes.len();
+            return None;
+        }
+        let error = AsAsciiStrError::new(rest, valid_up_to);
+        let start = self.offset + valid_up_to;
+        let end = start + error.error_len().unwrap_or(1);
+        self.offset = end;
+        Some(start..end)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.bytes.len() - self.offset;
+        (0, Some(remaining))
+    }
+}
+
+impl<'a> FusedIterator for NonAsciiRanges<'a> {}
+
+/// A summary of the non-ASCII content of a byte slice.
+///
+/// # Examples
+/// ```
+/// # use ascii::AsciiValidationReport;
+/// let report = AsciiValidationReport::new(b"id,name\n7,Ren\xc3\xa9e\n8,\xff\n");
+/// assert!(!report.is_ascii());
+/// assert_eq!(report.first_non_ascii(), Some(13));
+/// assert_eq!(report.non_ascii_sequences(), 2);
+/// assert_eq!(report.non_ascii_chars(), 1);
+/// assert_eq!(report.invalid_utf8_bytes(), 1);
+/// assert_eq!(report.non_ascii_bytes(), 3);
+/// assert_eq!(report.ascii_bytes(), 18);
+/// assert_eq!(
+///     report.to_string(),
+///     "2 non-ASCII sequences (3 bytes) in 21 bytes, the first at offset 13"
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AsciiValidationReport {
+    len: usize,
+    first_non_ascii: Option<usize>,
+    non_ascii_bytes: usize,
+    non_ascii_chars: usize,
+    invalid_utf8_bytes: usize,
+}
+
+impl AsciiValidationReport {
+    /// Scans all of `bytes` and counts its non-ASCII sequences.
+    #[must_use]
+    pub fn new<B: AsRef<[u8]> + ?Sized>(bytes: &B) -> Self {
+        let bytes = bytes.as_ref();
+        let mut report = AsciiValidationReport {
+            len: bytes.len(),
+            ..AsciiValidationReport::default()
+        };
+        for range in non_ascii_ranges(bytes) {
+            if report.first_non_ascii.is_none() {
+                report.first_non_ascii = Some(range.start);
+            }
+            let len = range.len();
+            report.non_ascii_bytes += len;
+            if len == 1 {
+                report.invalid_utf8_bytes += 1;
+            } else {
+                report.non_ascii_chars += 1;
+            }
+        }
+        report
+    }
+
+    /// Returns `true` if all bytes are ASCII.
+    #[inline]
+    #[must_use]
+    pub const fn is_ascii(&self) -> bool {
+        self.non_ascii_bytes == 0
+    }
+
+    /// Returns the total number of bytes that were scanned.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no bytes were scanned.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the index of the first non-ASCII byte,
+    /// which is the same as [`AsAsciiStrError::valid_up_to()`].
+    #[inline]
+    #[must_use]
+    pub const fn first_non_ascii(&self) -> Option<usize> {
+        self.first_non_ascii
+    }
+
+    /// Returns the number of ASCII bytes.
+    #[inline]
+    #[must_use]
+    pub const fn ascii_bytes(&self) -> usize {
+        self.len - self.non_ascii_bytes
+    }
+
+    /// Returns the number of non-ASCII bytes.
+    #[inline]
+    #[must_use]
+    pub const fn non_ascii_bytes(&self) -> usize {
+        self.non_ascii_bytes
+    }
+
+    /// Returns the number of ranges [`non_ascii_ranges()`] yields,
+    /// which is the sum of [`non_ascii_chars()`](#method.non_ascii_chars) and
+    /// [`invalid_utf8_bytes()`](#method.invalid_utf8_bytes).
+    #[inline]
+    #[must_use]
+    pub const fn non_ascii_sequences(&self) -> usize {
+        self.non_ascii_chars + self.invalid_utf8_bytes
+    }
+
+    /// Returns the number of valid UTF-8 encoded non-ASCII characters.
+    #[inline]
+    #[must_use]
+    pub const fn non_ascii_chars(&self) -> usize {
+        self.non_ascii_chars
+    }
+
+    /// Returns the number of non-ASCII bytes that are not part of a valid UTF-8 sequence.
+    #[inline]
+    #[must_use]
+    pub const fn invalid_utf8_bytes(&self) -> usize {
+        self.invalid_utf8_bytes
+    }
+}
+
+impl fmt::Display for AsciiValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.first_non_ascii {
+            None => write!(f, "all {} bytes are ASCII", self.len),
+            Some(first) => write!(
+                f,
+                "{} non-ASCII sequences ({} bytes) in {} bytes, the first at offset {}",
+                self.non_ascii_sequences(),
+                self.non_ascii_bytes,
+                self.len,
+                first
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{non_ascii_ranges, AsciiValidationReport};
+
+    #[test]
+    fn ranges() {
+        assert_eq!(non_ascii_ranges("").next(), None);
+        assert_eq!(non_ascii_ranges("all ascii here").next(), None);
+        let mut ranges = non_ascii_ranges("\u{1f600}x\u{7ff}");
+        assert_eq!(ranges.next(), Some(0..4));
+        assert_eq!(ranges.next(), Some(5..7));
+        assert_eq!(ranges.next(), None);
+        assert_eq!(ranges.next(), None);
+
+        // A truncated sequence is reported one byte at a time.
+        let ranges = non_ascii_ranges(b"\xe2\x82x\xe2\x82\xac");
+        assert!(ranges.eq([0..1, 1..2, 3..6].iter().cloned()));
+
+        // Non-ASCII bytes after a long ASCII prefix, to use the vectorized validation.
+        let mut long = [b'-'; 100];
+        long[40] = 0x80;
+        long[98] = 0xc3;
+        long[99] = 0xa9;
+        assert!(non_ascii_ranges(&long[..]).eq([40..41, 98..100].iter().cloned()));
+    }
+
+    #[test]
+    fn report() {
+        let report = AsciiValidationReport::new("plain");
+        assert!(report.is_ascii());
+        assert_eq!(report.first_non_ascii(), None);
+        assert_eq!(report.ascii_bytes(), 5);
+        assert_eq!(report.non_ascii_sequences(), 0);
+
+        let report = AsciiValidationReport::new("\u{e9}\u{e9}\u{1f600}");
+        assert_eq!(report.len(), 8);
+        assert_eq!(report.first_non_ascii(), Some(0));
+        assert_eq!(report.non_ascii_chars(), 3);
+        assert_eq!(report.invalid_utf8_bytes(), 0);
+        assert_eq!(report.ascii_bytes(), 0);
+
+        assert!(AsciiValidationReport::new(b"").is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn display() {
+        let report = AsciiValidationReport::new("ok");
+        assert_eq!(report.to_string(), "all 2 bytes are ASCII");
+    }
+}
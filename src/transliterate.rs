@@ -0,0 +1,245 @@
+use unicode_normalization::char::{decompose_compatible, is_combining_mark};
+
+use ascii_char::{AsciiChar, ToAsciiChar};
+use ascii_str::AsAsciiStr;
+use ascii_string::AsciiString;
+
+/// What transliteration does with characters that have no ASCII equivalent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransliterationFallback {
+    /// Replace the character with the given ASCII character.
+    Replace(AsciiChar),
+    /// Leave out the character.
+    Drop,
+    /// Replace the character with its `\u{NNNN}` escape, as produced by
+    /// [`char::escape_unicode()`].
+    UnicodeEscape,
+}
+
+impl Default for TransliterationFallback {
+    /// Replaces characters with `?`.
+    fn default() -> Self {
+        TransliterationFallback::Replace(AsciiChar::Question)
+    }
+}
+
+/// Approximates Unicode text with ASCII.
+///
+/// Transliteration
+///
+/// * strips diacritics (`é` becomes `e`) and other combining marks,
+/// * expands ligatures and letters that have no ASCII base letter
+///   (`æ` becomes `ae`, `ß` becomes `ss` and `ø` becomes `o`),
+/// * replaces compatibility characters with their plain equivalents
+///   (`ﬁ` becomes `fi`, `²` becomes `2` and a no-break space becomes a space),
+/// * and maps typographic punctuation such as smart quotes, dashes and ellipses
+///   to their ASCII counterparts.
+///
+/// Characters that can't be approximated, such as most non-Latin scripts, are handled according
+/// to a [`TransliterationFallback`].
+///
+/// This trait is only available with the `transliteration` feature.
+///
+/// # Examples
+/// ```
+/// # use ascii::{AsciiChar, Transliterate, TransliterationFallback};
+/// assert_eq!("Ærøskøbing – “Straße”…".transliterate_ascii(), "AEroskobing - \"Strasse\"...");
+/// assert_eq!('ﬀ'.transliterate_ascii(), "ff");
+///
+/// let fallback = TransliterationFallback::Replace(AsciiChar::UnderScore);
+/// assert_eq!("東京 Tōkyō".transliterate_ascii_with(fallback), "__ Tokyo");
+/// let fallback = TransliterationFallback::UnicodeEscape;
+/// assert_eq!("☃".transliterate_ascii_with(fallback), "\\u{2603}");
+/// ```
+pub trait Transliterate {
+    /// Transliterates to ASCII, replacing characters without an ASCII equivalent with `?`.
+    #[must_use]
+    fn transliterate_ascii(&self) -> AsciiString {
+        self.transliterate_ascii_with(TransliterationFallback::default())
+    }
+
+    /// Transliterates to ASCII, handling characters without an ASCII equivalent
+    /// as described by `fallback`.
+    #[must_use]
+    fn transliterate_ascii_with(&self, fallback: TransliterationFallback) -> AsciiString;
+}
+
+impl Transliterate for str {
+    fn transliterate_ascii_with(&self, fallback: TransliterationFallback) -> AsciiString {
+        AsciiString::from_transliterated(self, fallback)
+    }
+}
+
+impl Transliterate for char {
+    fn transliterate_ascii_with(&self, fallback: TransliterationFallback) -> AsciiString {
+        let mut ascii = AsciiString::new();
+        push_transliterated(&mut ascii, *self, fallback);
+        ascii
+    }
+}
+
+/// Appends the transliteration of `c` to `ascii`.
+pub(crate) fn push_transliterated(
+    ascii: &mut AsciiString,
+    c: char,
+    fallback: TransliterationFallback,
+) {
+    if let Ok(ch) = c.to_ascii_char() {
+        ascii.push(ch);
+    } else if let Some(replacement) = replacement(c) {
+        push_replacement(ascii, replacement);
+    } else {
+        let start = ascii.len();
+        let (mut approximated, mut unknown) = (false, false);
+        decompose_compatible(c, |part| {
+            if let Ok(ch) = part.to_ascii_char() {
+                ascii.push(ch);
+                approximated = true;
+            } else if let Some(replacement) = replacement(part) {
+                push_replacement(ascii, replacement);
+                approximated = true;
+            } else if !is_combining_mark(part) {
+                push_fallback(ascii, part, fallback);
+                unknown = true;
+            }
+        });
+        // Nothing in the decomposition was approximated, so it's `c` itself that is unknown.
+        if unknown && !approximated {
+            ascii.truncate(start);
+            push_fallback(ascii, c, fallback);
+        }
+    }
+}
+
+fn push_replacement(ascii: &mut AsciiString, replacement: &'static str) {
+    // SAFETY: The `replacements` test checks that all replacements are ASCII.
+    ascii.push_str(unsafe { replacement.as_ascii_str_unchecked() });
+}
+
+fn push_fallback(ascii: &mut AsciiString, c: char, fallback: TransliterationFallback) {
+    match fallback {
+        TransliterationFallback::Replace(ch) => ascii.push(ch),
+        TransliterationFallback::Drop => {}
+        TransliterationFallback::UnicodeEscape => {
+            for escaped in c.escape_unicode() {
+                // SAFETY: `escape_unicode()` only produces `\`, `u`, braces and hex digits.
+                ascii.push(unsafe { escaped.to_ascii_char_unchecked() });
+            }
+        }
+    }
+}
+
+/// Returns the ASCII replacement for characters that compatibility decomposition
+/// doesn't turn into ASCII.
+fn replacement(c: char) -> Option<&'static str> {
+    Some(match c {
+        // Ligatures and letters without a decomposition
+        'Æ' | 'Ǽ' | 'Ǣ' => "AE",
+        'æ' | 'ǽ' | 'ǣ' => "ae",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'ß' => "ss",
+        'ẞ' => "SS",
+        'Ø' | 'Ǿ' => "O",
+        'ø' | 'ǿ' => "o",
+        'Đ' | 'Ð' => "D",
+        'đ' | 'ð' => "d",
+        'Ħ' => "H",
+        'ħ' => "h",
+        'ı' => "i",
+        'Ł' => "L",
+        'ł' => "l",
+        'Ŋ' => "NG",
+        'ŋ' => "ng",
+        'ĸ' => "q",
+        'Ŧ' => "T",
+        'ŧ' => "t",
+        'Þ' => "TH",
+        'þ' => "th",
+        'Ƒ' => "F",
+        'ƒ' => "f",
+        // Quotes
+        '‘' | '’' | '‚' | '‛' | '′' | '‹' | '›' => "'",
+        '“' | '”' | '„' | '‟' | '″' | '«' | '»' => "\"",
+        // Dashes and hyphens
+        '‐' | '‑' | '‒' | '–' | '—' | '―' | '−' | '⁃' => "-",
+        // Other punctuation and symbols
+        '•' | '·' | '∙' => "*",
+        '⁄' | '∕' | '÷' => "/",
+        '×' => "x",
+        '¡' => "!",
+        '¿' => "?",
+        '¦' => "|",
+        '©' => "(C)",
+        '®' => "(R)",
+        '°' => "deg",
+        '±' => "+/-",
+        '£' => "GBP",
+        '¥' => "JPY",
+        '€' => "EUR",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{replacement, Transliterate, TransliterationFallback};
+    use AsciiChar;
+
+    #[test]
+    fn replacements() {
+        for c in '\u{80}'..'\u{10000}' {
+            if let Some(replacement) = replacement(c) {
+                assert!(replacement.is_ascii(), "{:?}", c);
+                assert!(!replacement.is_empty(), "{:?}", c);
+            }
+        }
+    }
+
+    #[test]
+    fn transliterate() {
+        assert_eq!("plain ascii\n".transliterate_ascii(), "plain ascii\n");
+        assert_eq!("Crème Brûlée".transliterate_ascii(), "Creme Brulee");
+        assert_eq!("Ångström".transliterate_ascii(), "Angstrom");
+        assert_eq!("Łódź".transliterate_ascii(), "Lodz");
+        assert_eq!("Dvořák".transliterate_ascii(), "Dvorak");
+        assert_eq!("Phở Việt".transliterate_ascii(), "Pho Viet");
+        assert_eq!(
+            "Æsir œuvre Fußball".transliterate_ascii(),
+            "AEsir oeuvre Fussball"
+        );
+        assert_eq!("ﬁnal ﬂow Ĳssel".transliterate_ascii(), "final flow IJssel");
+        assert_eq!("x² ½ ™".transliterate_ascii(), "x2 1/2 TM");
+        assert_eq!("‘it’s’ “quoted”".transliterate_ascii(), "'it's' \"quoted\"");
+        assert_eq!("a–b—c…".transliterate_ascii(), "a-b-c...");
+        assert_eq!("\u{a0}\u{2009}".transliterate_ascii(), "  ");
+        assert_eq!("e\u{301}".transliterate_ascii(), "e");
+        assert_eq!("".transliterate_ascii(), "");
+        assert_eq!('ß'.transliterate_ascii(), "ss");
+    }
+
+    #[test]
+    fn fallback() {
+        let text = "Привет, мир 🙂!";
+        assert_eq!(text.transliterate_ascii(), "??????, ??? ?!");
+        assert_eq!(
+            text.transliterate_ascii_with(TransliterationFallback::Drop),
+            ",  !"
+        );
+        assert_eq!(
+            text.transliterate_ascii_with(TransliterationFallback::Replace(AsciiChar::Asterisk)),
+            "******, *** *!"
+        );
+        assert_eq!(
+            "a🙂".transliterate_ascii_with(TransliterationFallback::UnicodeEscape),
+            "a\\u{1f642}"
+        );
+
+        // Characters that decompose into other unknown characters.
+        assert_eq!("한й".transliterate_ascii(), "??");
+        assert_eq!(
+            "한й".transliterate_ascii_with(TransliterationFallback::UnicodeEscape),
+            "\\u{d55c}\\u{439}"
+        );
+    }
+}
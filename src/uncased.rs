@@ -0,0 +1,360 @@
+#[cfg(feature = "alloc")]
+use alloc::borrow::{Borrow, ToOwned};
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "alloc")]
+use core::ops::Deref;
+use core::{fmt, mem};
+
+use ascii_str::AsciiStr;
+#[cfg(feature = "alloc")]
+use ascii_string::AsciiString;
+
+/// A borrowed [`AsciiStr`] that ignores ASCII case when compared, ordered or hashed.
+///
+/// Ordering behaves as if both strings were converted to lowercase,
+/// which makes it consistent with [`AsciiStr::cmp_ignore_ascii_case()`].
+/// The original case is kept and visible through [`Display`](fmt::Display),
+/// [`Debug`](fmt::Debug) and [`as_ascii_str()`](#method.as_ascii_str).
+///
+/// # Examples
+/// ```
+/// # use ascii::{AsciiStr, UncasedAsciiStr};
+/// let a = UncasedAsciiStr::new(AsciiStr::from_ascii("Content-Type").unwrap());
+/// let b = UncasedAsciiStr::new(AsciiStr::from_ascii("content-type").unwrap());
+/// assert_eq!(a, b);
+/// assert_eq!(a.to_string(), "Content-Type");
+/// ```
+#[repr(transparent)]
+pub struct UncasedAsciiStr {
+    string: AsciiStr,
+}
+
+impl UncasedAsciiStr {
+    /// Wraps an `AsciiStr` without copying it.
+    #[inline]
+    #[must_use]
+    pub const fn new(string: &AsciiStr) -> &Self {
+        // SAFETY: `UncasedAsciiStr` is a `repr(transparent)` wrapper around `AsciiStr`.
+        unsafe { mem::transmute(string) }
+    }
+
+    /// Returns the wrapped string with its original case.
+    #[inline]
+    #[must_use]
+    pub const fn as_ascii_str(&self) -> &AsciiStr {
+        &self.string
+    }
+
+    /// Copies the string into an owned [`UncasedAsciiString`].
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[must_use]
+    pub fn to_uncased_ascii_string(&self) -> UncasedAsciiString {
+        UncasedAsciiString::from(self.string.to_ascii_string())
+    }
+}
+
+impl PartialEq for UncasedAsciiStr {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.string.eq_ignore_ascii_case(&other.string)
+    }
+}
+
+impl Eq for UncasedAsciiStr {}
+
+impl PartialOrd for UncasedAsciiStr {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for UncasedAsciiStr {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.string.cmp_ignore_ascii_case(&other.string)
+    }
+}
+
+impl Hash for UncasedAsciiStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for ch in self.string.chars() {
+            state.write_u8(ch.to_ascii_lowercase().as_byte());
+        }
+        // Like `str`, terminate with a byte that isn't ASCII so that
+        // `("ab", "c")` and `("a", "bc")` hash differently.
+        state.write_u8(0xff);
+    }
+}
+
+impl fmt::Display for UncasedAsciiStr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.string, f)
+    }
+}
+
+impl fmt::Debug for UncasedAsciiStr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.string, f)
+    }
+}
+
+impl AsRef<AsciiStr> for UncasedAsciiStr {
+    #[inline]
+    fn as_ref(&self) -> &AsciiStr {
+        &self.string
+    }
+}
+
+impl<'a> From<&'a AsciiStr> for &'a UncasedAsciiStr {
+    #[inline]
+    fn from(string: &AsciiStr) -> &UncasedAsciiStr {
+        UncasedAsciiStr::new(string)
+    }
+}
+
+impl<'a> From<&'a UncasedAsciiStr> for &'a AsciiStr {
+    #[inline]
+    fn from(uncased: &UncasedAsciiStr) -> &AsciiStr {
+        &uncased.string
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToOwned for UncasedAsciiStr {
+    type Owned = UncasedAsciiString;
+
+    #[inline]
+    fn to_owned(&self) -> UncasedAsciiString {
+        self.to_uncased_ascii_string()
+    }
+}
+
+/// An owned [`AsciiString`] that ignores ASCII case when compared, ordered or hashed.
+///
+/// It dereferences to [`UncasedAsciiStr`], which has the same comparison semantics,
+/// and implements `Borrow<UncasedAsciiStr>`, so maps keyed by `UncasedAsciiString`
+/// can be looked up with any `&AsciiStr` without allocating.
+///
+/// # Examples
+/// ```
+/// # use ascii::{AsciiStr, UncasedAsciiStr, UncasedAsciiString};
+/// use std::collections::HashMap;
+///
+/// let mut headers = HashMap::new();
+/// let name = AsciiStr::from_ascii("Content-Type").unwrap();
+/// headers.insert(UncasedAsciiString::from(name), "text/html");
+///
+/// let lookup = AsciiStr::from_ascii("content-type").unwrap();
+/// assert_eq!(headers.get(UncasedAsciiStr::new(lookup)), Some(&"text/html"));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Default)]
+#[repr(transparent)]
+pub struct UncasedAsciiString {
+    string: AsciiString,
+}
+
+#[cfg(feature = "alloc")]
+impl UncasedAsciiString {
+    /// Returns the wrapped string with its original case.
+    #[inline]
+    #[must_use]
+    pub fn as_ascii_str(&self) -> &AsciiStr {
+        &self.string
+    }
+
+    /// Unwraps the string, which keeps its original case.
+    #[inline]
+    #[must_use]
+    pub fn into_ascii_string(self) -> AsciiString {
+        self.string
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Deref for UncasedAsciiString {
+    type Target = UncasedAsciiStr;
+
+    #[inline]
+    fn deref(&self) -> &UncasedAsciiStr {
+        UncasedAsciiStr::new(&self.string)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Borrow<UncasedAsciiStr> for UncasedAsciiString {
+    #[inline]
+    fn borrow(&self) -> &UncasedAsciiStr {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsRef<UncasedAsciiStr> for UncasedAsciiString {
+    #[inline]
+    fn as_ref(&self) -> &UncasedAsciiStr {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsRef<AsciiStr> for UncasedAsciiString {
+    #[inline]
+    fn as_ref(&self) -> &AsciiStr {
+        &self.string
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<AsciiString> for UncasedAsciiString {
+    #[inline]
+    fn from(string: AsciiString) -> Self {
+        UncasedAsciiString { string }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a AsciiStr> for UncasedAsciiString {
+    #[inline]
+    fn from(string: &AsciiStr) -> Self {
+        UncasedAsciiString::from(string.to_ascii_string())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<UncasedAsciiString> for AsciiString {
+    #[inline]
+    fn from(uncased: UncasedAsciiString) -> Self {
+        uncased.string
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PartialEq for UncasedAsciiString {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Eq for UncasedAsciiString {}
+
+#[cfg(feature = "alloc")]
+impl PartialOrd for UncasedAsciiString {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Ord for UncasedAsciiString {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Hash for UncasedAsciiString {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> PartialEq<&'a UncasedAsciiStr> for UncasedAsciiString {
+    #[inline]
+    fn eq(&self, other: &&'a UncasedAsciiStr) -> bool {
+        **self == **other
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> PartialEq<UncasedAsciiString> for &'a UncasedAsciiStr {
+    #[inline]
+    fn eq(&self, other: &UncasedAsciiString) -> bool {
+        **self == **other
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for UncasedAsciiString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.string, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for UncasedAsciiString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.string, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UncasedAsciiStr;
+    #[cfg(feature = "alloc")]
+    use super::UncasedAsciiString;
+    use core::cmp::Ordering;
+    use AsAsciiStr;
+
+    #[test]
+    fn compare() {
+        let a = UncasedAsciiStr::new("Accept".as_ascii_str().unwrap());
+        let b = UncasedAsciiStr::new("ACCEPT".as_ascii_str().unwrap());
+        let c = UncasedAsciiStr::new("accept-encoding".as_ascii_str().unwrap());
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a.cmp(b), Ordering::Equal);
+        assert_eq!(b.cmp(c), Ordering::Less);
+        assert_eq!(a.as_ascii_str(), "Accept");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_map_lookup() {
+        use std::collections::HashMap;
+
+        let mut map = HashMap::new();
+        for name in &["Host", "Content-Length", "X-Forwarded-For"] {
+            let name = name.as_ascii_str().unwrap();
+            map.insert(UncasedAsciiString::from(name), name.len());
+        }
+        let key = "content-LENGTH".as_ascii_str().unwrap();
+        assert_eq!(map.get(UncasedAsciiStr::new(key)), Some(&14));
+        let key = "X-FORWARDED-FOR".as_ascii_str().unwrap();
+        assert_eq!(map.get(UncasedAsciiStr::new(key)), Some(&15));
+        let key = "Hosts".as_ascii_str().unwrap();
+        assert_eq!(map.get(UncasedAsciiStr::new(key)), None);
+        // The inserted key keeps its case.
+        let key = "HOST".as_ascii_str().unwrap();
+        let (stored, _) = map.get_key_value(UncasedAsciiStr::new(key)).unwrap();
+        assert_eq!(stored.as_ascii_str(), "Host");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn btree_map_lookup() {
+        use alloc::collections::BTreeMap;
+
+        let mut map = BTreeMap::new();
+        map.insert(UncasedAsciiString::from("b".as_ascii_str().unwrap()), 2);
+        map.insert(UncasedAsciiString::from("A".as_ascii_str().unwrap()), 1);
+        map.insert(UncasedAsciiString::from("_".as_ascii_str().unwrap()), 0);
+        let keys: alloc::vec::Vec<_> = map.keys().map(|key| key.as_ascii_str().as_str()).collect();
+        assert_eq!(keys, ["_", "A", "b"]);
+        let key = "B".as_ascii_str().unwrap();
+        assert_eq!(map.get(UncasedAsciiStr::new(key)), Some(&2));
+    }
+}
@@ -0,0 +1,202 @@
+use core::mem;
+
+const WORD: usize = mem::size_of::<usize>();
+const HIGH_BITS: usize = usize::from_ne_bytes([0x80; WORD]);
+
+/// Returns the length of the longest prefix of `bytes` that is ASCII,
+/// which is `bytes.len()` if all of it is.
+///
+/// Unlike the const `AsciiStr::from_ascii_bytes()`, this checks a vector register or two
+/// `usize`s at a time, and only looks at individual bytes to find the exact position of the
+/// first non-ASCII byte once a block contains one.
+#[inline]
+pub(crate) fn ascii_prefix_len(bytes: &[u8]) -> usize {
+    #[cfg(all(
+        feature = "std",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        not(target_feature = "avx2")
+    ))]
+    {
+        if bytes.len() >= 32 && is_x86_feature_detected!("avx2") {
+            // SAFETY: We just checked that the CPU supports AVX2.
+            return unsafe { x86::avx2(bytes) };
+        }
+    }
+    simd(bytes)
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "avx2"
+))]
+#[inline]
+fn simd(bytes: &[u8]) -> usize {
+    // SAFETY: AVX2 is enabled at compile time.
+    unsafe { x86::avx2(bytes) }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2",
+    not(target_feature = "avx2")
+))]
+#[inline]
+fn simd(bytes: &[u8]) -> usize {
+    x86::sse2(bytes)
+}
+
+#[cfg(not(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+)))]
+#[inline]
+fn simd(bytes: &[u8]) -> usize {
+    // aarch64 also uses this, as the NEON intrinsics in `core::arch::aarch64`
+    // require Rust 1.59 and the MSRV is 1.56.1.
+    words(bytes)
+}
+
+/// Checks one byte at a time.
+fn bytes(bytes: &[u8]) -> usize {
+    bytes.iter().take_while(|b| b.is_ascii()).count()
+}
+
+/// Checks two `usize`s at a time.
+fn words(bytes: &[u8]) -> usize {
+    let mut checked = 0;
+    let mut chunks = bytes.chunks_exact(2 * WORD);
+    for chunk in &mut chunks {
+        #[allow(clippy::cast_ptr_alignment)] // Only read with `read_unaligned()`
+        let ptr = chunk.as_ptr().cast::<usize>();
+        // SAFETY: The chunk is long enough for two `usize`s, and `read_unaligned()`
+        //         doesn't require `ptr` to be aligned.
+        let (first, second) = unsafe { (ptr.read_unaligned(), ptr.add(1).read_unaligned()) };
+        if (first | second) & HIGH_BITS != 0 {
+            return checked + self::bytes(chunk);
+        }
+        checked += chunk.len();
+    }
+    checked + self::bytes(chunks.remainder())
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    #[cfg(all(target_arch = "x86", target_feature = "sse2"))]
+    use core::arch::x86::{__m128i, _mm_loadu_si128, _mm_movemask_epi8};
+    #[cfg(all(target_arch = "x86", any(feature = "std", target_feature = "avx2")))]
+    use core::arch::x86::{__m256i, _mm256_loadu_si256, _mm256_movemask_epi8};
+    #[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+    use core::arch::x86_64::{__m128i, _mm_loadu_si128, _mm_movemask_epi8};
+    #[cfg(all(target_arch = "x86_64", any(feature = "std", target_feature = "avx2")))]
+    use core::arch::x86_64::{__m256i, _mm256_loadu_si256, _mm256_movemask_epi8};
+
+    /// Checks 16 bytes at a time.
+    ///
+    /// `movemask` collects the high bit of every byte, so the index of its lowest set bit
+    /// is the index of the first non-ASCII byte.
+    #[cfg(target_feature = "sse2")]
+    pub(super) fn sse2(bytes: &[u8]) -> usize {
+        let mut checked = 0;
+        let mut chunks = bytes.chunks_exact(16);
+        for chunk in &mut chunks {
+            // SAFETY: SSE2 is enabled at compile time, and the chunk is 16 bytes long.
+            #[allow(clippy::cast_ptr_alignment)] // `_mm_loadu_si128` doesn't require alignment
+            let mask =
+                unsafe { _mm_movemask_epi8(_mm_loadu_si128(chunk.as_ptr().cast::<__m128i>())) };
+            if mask != 0 {
+                return checked + mask.trailing_zeros() as usize;
+            }
+            checked += chunk.len();
+        }
+        checked + super::words(chunks.remainder())
+    }
+
+    /// Checks 32 bytes at a time.
+    ///
+    /// # Safety
+    /// The CPU must support AVX2.
+    #[cfg(any(feature = "std", target_feature = "avx2"))]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn avx2(bytes: &[u8]) -> usize {
+        let mut checked = 0;
+        let mut chunks = bytes.chunks_exact(32);
+        for chunk in &mut chunks {
+            // SAFETY: The caller guarantees AVX2 is supported, and the chunk is 32 bytes long.
+            #[allow(clippy::cast_ptr_alignment)] // `_mm256_loadu_si256` doesn't require alignment
+            let mask = unsafe {
+                _mm256_movemask_epi8(_mm256_loadu_si256(chunk.as_ptr().cast::<__m256i>()))
+            };
+            if mask != 0 {
+                return checked + mask.trailing_zeros() as usize;
+            }
+            checked += chunk.len();
+        }
+        #[cfg(target_feature = "sse2")]
+        let remainder = sse2(chunks.remainder());
+        #[cfg(not(target_feature = "sse2"))]
+        let remainder = super::words(chunks.remainder());
+        checked + remainder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ascii_prefix_len, bytes, words, WORD};
+
+    /// Checks `check` against the byte-at-a-time implementation, for all lengths up to 100
+    /// and all start offsets up to 32, with a non-ASCII byte at every position.
+    ///
+    /// Miri only checks the start offsets within a word and lengths up to 40,
+    /// which still cover the unaligned starts and a 32-byte block with a remainder.
+    fn check_against_bytes(check: &dyn Fn(&[u8]) -> usize) {
+        let (starts, max_len) = if cfg!(miri) { (WORD, 40) } else { (32, 100) };
+        let mut buf = [b'a'; 140];
+        for start in 0..starts {
+            for len in 0..max_len {
+                let slice = &mut buf[start..start + len];
+                assert_eq!(check(slice), len);
+                for non_ascii in 0..len {
+                    slice[non_ascii] = 0x80;
+                    assert_eq!(check(slice), non_ascii);
+                    assert_eq!(check(slice), bytes(slice));
+                    // A later non-ASCII byte must not change the result.
+                    if let Some(last) = slice.last_mut() {
+                        *last = 0xff;
+                    }
+                    assert_eq!(check(slice), non_ascii);
+                    for b in slice.iter_mut() {
+                        *b = b'a';
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn words_finds_first_non_ascii() {
+        check_against_bytes(&words);
+    }
+
+    #[test]
+    fn dispatch_finds_first_non_ascii() {
+        check_against_bytes(&ascii_prefix_len);
+    }
+
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ))]
+    #[test]
+    fn sse2_finds_first_non_ascii() {
+        check_against_bytes(&super::x86::sse2);
+    }
+
+    #[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+    #[test]
+    fn avx2_finds_first_non_ascii() {
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: We just checked that the CPU supports AVX2.
+            check_against_bytes(&|bytes| unsafe { super::x86::avx2(bytes) });
+        }
+    }
+}